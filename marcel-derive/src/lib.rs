@@ -0,0 +1,130 @@
+//! Derive macros of the `marcel` theme library.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as Tokens;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, LitStr, Variant};
+
+/// Style categories of a theme.
+const CATEGORIES: &[&str] = &[
+    "border",
+    "button",
+    "container",
+    "panegrid",
+    "picklist",
+    "progressbar",
+    "scrollable",
+    "text",
+    "textinput",
+    "tooltip",
+];
+
+/// Derives `marcel::StyleKeys` for an enum of style keys.
+/// Each variant declares its category and key with a `style` attribute.
+///
+/// ```ignore
+/// #[derive(StyleKeys)]
+/// enum AppStyle {
+///     #[style(button = "primary")]
+///     Primary,
+///
+///     #[style(textinput = "search")]
+///     Search,
+/// }
+/// ```
+#[proc_macro_derive(StyleKeys, attributes(style))]
+pub fn derive_style_keys(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match style_keys(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Implements `StyleKeys` for an enum.
+fn style_keys(input: &DeriveInput) -> syn::Result<Tokens> {
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => {
+            return Err(Error::new_spanned(
+                input,
+                "`StyleKeys` can only be derived for enums",
+            ))
+        }
+    };
+
+    let mut keys = Vec::new();
+    let mut categories = Vec::new();
+    let mut required = Vec::new();
+
+    for variant in &data.variants {
+        let (category, key) = style(variant)?;
+
+        let ident = &variant.ident;
+        let pattern = match &variant.fields {
+            Fields::Named(_) => quote!(Self::#ident { .. }),
+            Fields::Unnamed(_) => quote!(Self::#ident(..)),
+            Fields::Unit => quote!(Self::#ident),
+        };
+
+        let name = category.to_string();
+        keys.push(quote!(#pattern => #key));
+        categories.push(quote!(#pattern => #name));
+        required.push(quote!(contract.#category.push(#key.into());));
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::marcel::StyleKeys for #name #ty_generics #where_clause {
+            fn contract() -> ::marcel::StyleContract {
+                let mut contract = ::marcel::StyleContract::default();
+                #(#required)*
+                contract
+            }
+
+            fn key(&self) -> &'static str {
+                match *self {
+                    #(#keys,)*
+                }
+            }
+
+            fn category(&self) -> &'static str {
+                match *self {
+                    #(#categories,)*
+                }
+            }
+        }
+    })
+}
+
+/// Parses the category and key of a variant from its `style` attribute.
+fn style(variant: &Variant) -> syn::Result<(syn::Ident, LitStr)> {
+    let mut style = None;
+
+    for attr in variant
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("style"))
+    {
+        attr.parse_nested_meta(|meta| {
+            let category = match meta.path.get_ident() {
+                Some(ident) if CATEGORIES.contains(&ident.to_string().as_str()) => ident.clone(),
+                _ => return Err(meta.error("unknown style category")),
+            };
+
+            if style.is_some() {
+                return Err(meta.error("a variant can only have one style key"));
+            }
+
+            style = Some((category, meta.value()?.parse()?));
+            Ok(())
+        })?;
+    }
+
+    style.ok_or_else(|| {
+        Error::new_spanned(variant, "missing `#[style(category = \"key\")]` attribute")
+    })
+}
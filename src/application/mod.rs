@@ -1,33 +1,108 @@
 use iced::application::StyleSheet;
 
-use crate::{Color, Theme};
+use crate::{serial::Number, theme::cascade, Color, Error, Theme};
+
+use std::collections::HashMap;
 
 pub mod serial;
 
+pub use serial::Defaults;
+
 #[derive(Clone, Default, Debug)]
 pub struct Application {
+    /// Background color.
     pub background_color: Color,
+
+    /// Default text color.
     pub text_color: Color,
+
+    /// Default text size.
+    pub text_size: Option<u16>,
+
+    /// Reference to the default font.
+    pub font: Option<String>,
+
+    /// Default style keys of each widget.
+    pub defaults: Defaults,
 }
 
 impl Application {
-    pub fn create(serial: &serial::Application, theme: &Theme) -> Result<Self, ()> {
-        let bg = theme
-            .color
-            .get(serial.background_color.as_str())
-            .ok_or_else(|| ())?;
-        let text = theme
-            .color
-            .get(serial.background_color.as_str())
-            .ok_or_else(|| ())?;
+    /// Attempts to create a theme from its &serialized version.
+    /// The default style keys must exist in the theme, so this must be created last.
+    pub fn create(serial: &serial::Application, theme: &Theme) -> Result<Self, Error> {
+        let color = |field, key: &String| {
+            theme.color_key(key).map_err(|_| Error::UnknownKey {
+                section: "application",
+                field,
+                key: key.clone(),
+            })
+        };
+
+        let bg = color("background_color", &serial.background_color)?;
+        let text = color("text_color", &serial.text_color)?;
+
+        let text_size = match &serial.text_size {
+            Some(size) => {
+                let size = theme.number(size).map_err(|_| Error::UnknownKey {
+                    section: "application",
+                    field: "text_size",
+                    key: match size {
+                        Number::Value(value) => value.to_string(),
+                        Number::Expression(expression) => expression.clone(),
+                    },
+                })?;
+
+                Some(size.round() as u16)
+            }
+            _ => None,
+        };
+
+        // Check that all the default styles exist.
+        let (d, t) = (&serial.defaults, theme);
+
+        exists("application", &d.application, "container", &t.container)?;
+        exists("button", &d.button, "button", &t.button)?;
+        exists("checkbox", &d.checkbox, "button", &t.button)?;
+        exists("container", &d.container, "container", &t.container)?;
+        exists("panegrid", &d.panegrid, "panegrid", &t.panegrid)?;
+        exists("picklist", &d.picklist, "picklist", &t.picklist)?;
+        exists("progressbar", &d.progressbar, "progressbar", &t.progressbar)?;
+        exists("radio", &d.radio, "button", &t.button)?;
+        exists("rule", &d.rule, "border", &t.border)?;
+        exists("scrollable", &d.scrollable, "scrollable", &t.scrollable)?;
+        exists("slider", &d.slider, "button", &t.button)?;
+        exists("svg", &d.svg, "text", &t.text)?;
+        exists("text", &d.text, "text", &t.text)?;
+        exists("textinput", &d.textinput, "textinput", &t.textinput)?;
+        exists("toggler", &d.toggler, "button", &t.button)?;
 
         Ok(Self {
-            background_color: bg.clone(),
-            text_color: text.clone(),
+            background_color: bg,
+            text_color: text,
+            text_size,
+            font: serial.font.clone(),
+            defaults: d.clone(),
         })
     }
 }
 
+/// Checks that the default style of a widget is defined in its category.
+fn exists<T>(
+    widget: &'static str,
+    key: &Option<String>,
+    category: &'static str,
+    map: &HashMap<String, T>,
+) -> Result<(), Error> {
+    match key {
+        Some(key) if cascade(map, key).is_none() => Err(Error::MissingDefault {
+            widget,
+            category,
+            key: key.clone(),
+        }),
+        _ => Ok(()),
+    }
+}
+
 impl StyleSheet for Application {
     type Style = iced::Theme;
 
@@ -38,3 +113,55 @@ impl StyleSheet for Application {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn application(text: &str, defaults: &str) -> serial::Application {
+        ron::from_str(&format!(
+            r#"(background_color: "white", text_color: "{}", defaults: {})"#,
+            text, defaults
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn defaults() {
+        let mut theme = Theme::new();
+        theme.text.insert("title".into(), Color(0, 0, 0, 1.0));
+
+        let application = application("black", r#"(text: Some("title.large"))"#);
+        assert!(Application::create(&application, &theme).is_ok());
+    }
+
+    #[test]
+    fn missing_defaults() {
+        let application = application("black", r#"(checkbox: Some("check"))"#);
+        let error = Application::create(&application, &Theme::new()).unwrap_err();
+
+        assert_eq!(
+            error,
+            Error::MissingDefault {
+                widget: "checkbox",
+                category: "button",
+                key: "check".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn unknown_colors() {
+        let application = application("foreground", "()");
+        let error = Application::create(&application, &Theme::new()).unwrap_err();
+
+        assert_eq!(
+            error,
+            Error::UnknownKey {
+                section: "application",
+                field: "text_color",
+                key: "foreground".into(),
+            }
+        );
+    }
+}
@@ -1,7 +1,135 @@
+use crate::serial::Number;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Application {
+    /// Key to the background color.
     pub background_color: String,
+
+    /// Key to the default text color.
     pub text_color: String,
+
+    /// Default text size.
+    #[serde(default)]
+    pub text_size: Option<Number>,
+
+    /// Reference to the default font (e.g. its file name in the font folder of the theme).
+    #[serde(default)]
+    pub font: Option<String>,
+
+    /// Default style keys of each widget.
+    #[serde(default)]
+    pub defaults: Defaults,
+}
+
+/// Fields of the application section overridden by a conditional override (e.g. the font of
+/// each platform). The fields that are not given keep their value.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApplicationOverride {
+    /// Key to the background color.
+    pub background_color: Option<String>,
+
+    /// Key to the default text color.
+    pub text_color: Option<String>,
+
+    /// Default text size.
+    pub text_size: Option<Number>,
+
+    /// Reference to the default font.
+    pub font: Option<String>,
+
+    /// Default style keys of each widget, which replace all the default keys.
+    pub defaults: Option<Defaults>,
+}
+
+impl Application {
+    /// Overrides the fields given by an application override.
+    pub(crate) fn apply(&mut self, fields: ApplicationOverride) {
+        if let Some(background_color) = fields.background_color {
+            self.background_color = background_color;
+        }
+
+        if let Some(text_color) = fields.text_color {
+            self.text_color = text_color;
+        }
+
+        if let Some(text_size) = fields.text_size {
+            self.text_size = Some(text_size);
+        }
+
+        if let Some(font) = fields.font {
+            self.font = Some(font);
+        }
+
+        if let Some(defaults) = fields.defaults {
+            self.defaults = defaults;
+        }
+    }
+}
+
+/// Default style keys used by the widgets that do not select a style.
+/// The keys refer to the category used to style each widget.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Defaults {
+    /// Key to the default application style (a container style).
+    #[serde(default)]
+    pub application: Option<String>,
+
+    /// Key to the default button style.
+    #[serde(default)]
+    pub button: Option<String>,
+
+    /// Key to the default checkbox style (a button style).
+    #[serde(default)]
+    pub checkbox: Option<String>,
+
+    /// Key to the default container style.
+    #[serde(default)]
+    pub container: Option<String>,
+
+    /// Key to the default pane grid style.
+    #[serde(default)]
+    pub panegrid: Option<String>,
+
+    /// Key to the default picklist style.
+    #[serde(default)]
+    pub picklist: Option<String>,
+
+    /// Key to the default progress bar style.
+    #[serde(default)]
+    pub progressbar: Option<String>,
+
+    /// Key to the default radio style (a button style).
+    #[serde(default)]
+    pub radio: Option<String>,
+
+    /// Key to the default rule style (a border).
+    #[serde(default)]
+    pub rule: Option<String>,
+
+    /// Key to the default scrollable style.
+    #[serde(default)]
+    pub scrollable: Option<String>,
+
+    /// Key to the default slider style (a button style).
+    #[serde(default)]
+    pub slider: Option<String>,
+
+    /// Key to the default svg style (a text style).
+    #[serde(default)]
+    pub svg: Option<String>,
+
+    /// Key to the default text style.
+    #[serde(default)]
+    pub text: Option<String>,
+
+    /// Key to the default text input style.
+    #[serde(default)]
+    pub textinput: Option<String>,
+
+    /// Key to the default toggler style (a button style).
+    #[serde(default)]
+    pub toggler: Option<String>,
 }
@@ -2,7 +2,12 @@
 
 pub mod serial;
 
-use crate::{Color, Theme};
+use crate::{
+    theme::cross::{self, split, state, Shared},
+    Color, Theme,
+};
+
+use serial::BorderComponent;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Border {
@@ -18,14 +23,71 @@ pub struct Border {
 
 impl Border {
     /// Attempts to create a theme from its &serialized version.
-    pub fn create(serial: &serial::Border, theme: &Theme) -> Result<Self, ()> {
-        match theme.color.get(serial.color.as_str()) {
-            Some(color) => Ok(Self {
-                color: *color,
-                radius: serial.radius,
-                width: serial.width,
-            }),
-            _ => Err(()),
+    pub fn create(serial: &BorderComponent, theme: &Theme) -> Result<Self, ()> {
+        // Inherit the shared fields of a style of another category.
+        if let Some((reference, _)) = serial.parent() {
+            if let (Some(category), name) = split(reference, "border") {
+                let shared = theme.shared(category, name, state("border", 0))?;
+                return Self::cross(serial, shared, theme);
+            }
+        }
+
+        match serial {
+            BorderComponent::Defined(border) => Self::from(border, theme),
+
+            BorderComponent::Inherited(name) => theme.border_key(name),
+
+            BorderComponent::Extends {
+                from,
+                color,
+                radius,
+                width,
+            } => {
+                let mut border = theme.border_key(from)?;
+
+                if let Some(key) = color {
+                    border.color = theme.color_key(key)?;
+                }
+
+                if let Some(radius) = radius {
+                    border.radius = theme.number(radius)?;
+                }
+
+                if let Some(width) = width {
+                    border.width = theme.number(width)?;
+                }
+
+                Ok(border)
+            }
         }
     }
+
+    /// Creates a style from the shared fields of a style of another category.
+    /// Borders do not share any field, so all of them must be overridden.
+    fn cross(serial: &BorderComponent, _: Shared, theme: &Theme) -> Result<Border, ()> {
+        let (color, radius, width) = match serial {
+            BorderComponent::Extends {
+                color,
+                radius,
+                width,
+                ..
+            } => (color, radius, width),
+            _ => (&None, &None, &None),
+        };
+
+        Ok(Border {
+            color: cross::color(theme, color, None)?,
+            radius: theme.number(radius.as_ref().ok_or(())?)?,
+            width: theme.number(width.as_ref().ok_or(())?)?,
+        })
+    }
+
+    /// Attempts to create a theme from its &serialized definition.
+    pub(crate) fn from(serial: &serial::Border, theme: &Theme) -> Result<Self, ()> {
+        Ok(Self {
+            color: theme.color_key(&serial.color)?,
+            radius: theme.number(&serial.radius)?,
+            width: theme.number(&serial.width)?,
+        })
+    }
 }
@@ -1,15 +1,95 @@
 //! `serde` compatible version of the border theme.
 
+use crate::{component::compact, serial::Number, theme::cross::overrides};
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub struct Border {
-    /// Key to the border color.
+    /// Key to the border color, or color literal.
     pub color: String,
 
     /// Radius of the border.
-    pub radius: f32,
+    pub radius: Number,
 
     /// Width of the border.
-    pub width: f32,
+    pub width: Number,
+}
+
+/// Border of a style, given as a key to a border of the theme or as an inline border.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum BorderValue {
+    /// Key to a border of the theme.
+    Key(String),
+
+    /// Inline border.
+    Literal(Box<Border>),
+}
+
+impl From<&str> for BorderValue {
+    fn from(key: &str) -> Self {
+        BorderValue::Key(key.into())
+    }
+}
+
+impl From<String> for BorderValue {
+    fn from(key: String) -> Self {
+        BorderValue::Key(key)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(remote = "Self", tag = "type")]
+pub enum BorderComponent {
+    /// The border is inherited from another border, overriding some of its fields.
+    Extends {
+        /// Key to the parent border.
+        from: String,
+
+        /// Key to the border color.
+        #[serde(default)]
+        color: Option<String>,
+
+        /// Radius of the border.
+        #[serde(default)]
+        radius: Option<Number>,
+
+        /// Width of the border.
+        #[serde(default)]
+        width: Option<Number>,
+    },
+
+    /// The border is defined.
+    Defined(Border),
+
+    /// The border is inherited from another border.
+    Inherited(String),
+}
+
+compact!(BorderComponent);
+
+impl BorderComponent {
+    /// Returns the reference to the parent style and its overridden fields, if it is inherited.
+    pub(crate) fn parent(&self) -> Option<(&str, Vec<&'static str>)> {
+        match self {
+            BorderComponent::Inherited(name) => Some((name, Vec::new())),
+
+            BorderComponent::Extends {
+                from,
+                color,
+                radius,
+                width,
+            } => Some((
+                from,
+                overrides(&[
+                    ("color", color.is_some()),
+                    ("radius", radius.is_some()),
+                    ("width", width.is_some()),
+                ]),
+            )),
+
+            BorderComponent::Defined(_) => None,
+        }
+    }
 }
@@ -2,7 +2,12 @@
 
 pub mod serial;
 
-use crate::{Border, Color, Theme};
+use crate::{
+    fallback,
+    operation::Transform,
+    theme::cross::{self, split, state, Shared},
+    Border, Color, Theme,
+};
 
 use iced::{
     widget::button::{Appearance, StyleSheet},
@@ -20,55 +25,85 @@ pub struct Button {
 
 impl Button {
     /// Attempts to create a theme from its &serialized version.
-    pub fn create(serial: &serial::Button, theme: &Theme) -> Result<Self, ()> {
+    pub fn create(serial: &serial::ButtonStyle, theme: &Theme) -> Result<Self, ()> {
+        // Expand the derived states.
+        let serial = serial.states();
+
         // Get all the themes.
         let active = Self::state(&serial.active, theme, 0)?;
         let hovered = Self::state(&serial.hovered, theme, 1)?;
         let pressed = Self::state(&serial.pressed, theme, 2)?;
         let disabled = Self::state(&serial.disabled, theme, 3)?;
 
-        // Find the first state theme that is not None.
-        let default = match (active, hovered, pressed, disabled) {
-            (Some(d), _, _, _) => d,
-            (_, Some(d), _, _) => d,
-            (_, _, Some(d), _) => d,
-            (_, _, _, Some(d)) => d,
+        // Fill the states that are not defined.
+        let rules = fallback::create(&serial.fallback, "button").map_err(|_| ())?;
+        let states = [active, hovered, pressed, disabled];
+        let state = fallback::resolve("button", states, &rules, theme)?;
+
+        Ok(Button { state })
+    }
 
-            _ => return Err(()),
+    /// Creates a state from the shared fields of a style of another category.
+    fn cross(serial: &ButtonComponent, shared: Shared, theme: &Theme) -> Result<ButtonState, ()> {
+        let (background, text, border) = match serial {
+            ButtonComponent::Extends {
+                background,
+                text,
+                border,
+                ..
+            } => (background, text, border),
+            _ => (&None, &None, &None),
         };
 
-        Ok(Button {
-            state: [
-                if active.is_some() {
-                    active.unwrap()
-                } else {
-                    default
-                },
-                if hovered.is_some() {
-                    hovered.unwrap()
-                } else {
-                    default
-                },
-                if pressed.is_some() {
-                    pressed.unwrap()
-                } else {
-                    default
-                },
-                if disabled.is_some() {
-                    disabled.unwrap()
-                } else {
-                    default
-                },
-            ],
+        Ok(ButtonState {
+            background: cross::color(theme, background, shared.background)?,
+            text: cross::color(theme, text, shared.text)?,
+            border: cross::border(theme, border, shared.border)?,
         })
     }
 
     fn state(serial: &ButtonComponent, theme: &Theme, index: usize) -> Result<Option<ButtonState>, ()> {
+        // Inherit the shared fields of a style of another category.
+        if let Some((reference, _)) = serial.parent() {
+            if let (Some(category), name) = split(reference, "button") {
+                let shared = theme.shared(category, name, state("button", index))?;
+                return Self::cross(serial, shared, theme).map(Some);
+            }
+        }
+
         match &serial {
             ButtonComponent::Defined(state) => Ok(Some(ButtonState::from(&state, &theme)?)),
 
-            ButtonComponent::Inherited(name) => match theme.button.get(name.as_str()) {
-                Some(button) => Ok(Some(button.state[index].clone())),
+            ButtonComponent::Inherited(name) => {
+                match theme.inherited("button", &theme.button, name) {
+                    Some(button) => Ok(Some(button.state[index].clone())),
+                    _ => Err(()),
+                }
+            }
+
+            ButtonComponent::Extends {
+                from,
+                background,
+                text,
+                border,
+            } => match theme.inherited("button", &theme.button, from) {
+                Some(button) => {
+                    let mut state = button.state[index];
+
+                    if let Some(key) = background {
+                        state.background = theme.color_key(key)?;
+                    }
+
+                    if let Some(key) = text {
+                        state.text = theme.color_key(key)?;
+                    }
+
+                    if let Some(key) = border {
+                        state.border = theme.border_value(key)?;
+                    }
+
+                    Ok(Some(state))
+                }
                 _ => Err(()),
             },
 
@@ -81,47 +116,19 @@ impl StyleSheet for Button {
     type Style = iced::Theme;
 
     fn active(&self, _: &Self::Style) -> Appearance {
-        Appearance {
-            shadow_offset: Vector::new(0.0, 0.0),
-            background: Some(self.state[0].background.into()),
-            border_radius: self.state[0].border.radius,
-            border_width: self.state[0].border.width,
-            border_color: self.state[0].border.color.into(),
-            text_color: self.state[0].text.into(),
-        }
+        self.state[0].into()
     }
 
     fn hovered(&self, _: &Self::Style) -> Appearance {
-        Appearance {
-            shadow_offset: Vector::new(0.0, 0.0),
-            background: Some(self.state[1].background.into()),
-            border_radius: self.state[1].border.radius,
-            border_width: self.state[1].border.width,
-            border_color: self.state[1].border.color.into(),
-            text_color: self.state[1].text.into(),
-        }
+        self.state[1].into()
     }
 
     fn pressed(&self, _: &Self::Style) -> Appearance {
-        Appearance {
-            shadow_offset: Vector::new(0.0, 0.0),
-            background: Some(self.state[2].background.into()),
-            border_radius: self.state[2].border.radius,
-            border_width: self.state[2].border.width,
-            border_color: self.state[2].border.color.into(),
-            text_color: self.state[2].text.into(),
-        }
+        self.state[2].into()
     }
 
     fn disabled(&self, _: &Self::Style) -> Appearance {
-        Appearance {
-            shadow_offset: Vector::new(0.0, 0.0),
-            background: Some(self.state[3].background.into()),
-            border_radius: self.state[3].border.radius,
-            border_width: self.state[3].border.width,
-            border_color: self.state[3].border.color.into(),
-            text_color: self.state[3].text.into(),
-        }
+        self.state[3].into()
     }
 }
 
@@ -137,26 +144,40 @@ pub struct ButtonState {
     pub border: Border,
 }
 
+impl From<ButtonState> for Appearance {
+    fn from(state: ButtonState) -> Self {
+        Appearance {
+            shadow_offset: Vector::new(0.0, 0.0),
+            background: Some(state.background.into()),
+            border_radius: state.border.radius,
+            border_width: state.border.width,
+            border_color: state.border.color.into(),
+            text_color: state.text.into(),
+        }
+    }
+}
+
+impl Transform for ButtonState {
+    fn transform(self, f: &dyn Fn(Color) -> Color) -> Self {
+        ButtonState {
+            background: f(self.background),
+            text: f(self.text),
+            border: self.border.transform(f),
+        }
+    }
+}
+
 impl ButtonState {
     /// Attempts to create a theme from its &serialized version.
     fn from(serial: &serial::ButtonState, theme: &Theme) -> Result<Self, ()> {
         // Get the background color.
-        let background = match theme.color.get(serial.background.as_str()) {
-            Some(color) => *color,
-            _ => return Err(()),
-        };
+        let background = theme.color_key(&serial.background)?;
 
         // Get the text color.
-        let text = match theme.color.get(serial.text.as_str()) {
-            Some(color) => *color,
-            _ => return Err(()),
-        };
+        let text = theme.color_key(&serial.text)?;
 
         // Get the background color.
-        let border = match theme.border.get(serial.border.as_str()) {
-            Some(border) => *border,
-            _ => return Err(()),
-        };
+        let border = theme.border_value(&serial.border)?;
 
         Ok(ButtonState {
             background,
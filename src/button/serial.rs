@@ -1,20 +1,91 @@
 //! Serial Button theme.
 
+use crate::{
+    component::{compact, derivable},
+    fallback::serial::derived,
+    serial::{BorderValue, Fallback},
+    theme::cross::overrides,
+};
+
 use serde::{Deserialize, Serialize};
 
+use std::{borrow::Cow, collections::HashMap};
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Button {
     /// Active state.
+    #[serde(default)]
     pub(crate) active: ButtonComponent,
 
     /// Hovered state.
+    #[serde(default)]
     pub(crate) hovered: ButtonComponent,
 
     /// Pressed state.
+    #[serde(default)]
     pub(crate) pressed: ButtonComponent,
 
     /// Disabled state.
+    #[serde(default)]
     pub(crate) disabled: ButtonComponent,
+
+    /// Fallback rules of the states that are not defined, mapped by the name of the state.
+    #[serde(default)]
+    pub(crate) fallback: HashMap<String, Fallback>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(remote = "Self", tag = "type")]
+pub enum ButtonStyle {
+    /// All the states are derived from a base state, applying color operations to it.
+    Derived {
+        /// Base (active) state.
+        base: ButtonComponent,
+
+        /// Color operations that derive the hovered state (e.g. `"lighten(8%)"`).
+        #[serde(default)]
+        hovered: Option<String>,
+
+        /// Color operations that derive the pressed state (e.g. `"darken(6%)"`).
+        #[serde(default)]
+        pressed: Option<String>,
+
+        /// Color operations that derive the disabled state (e.g. `"desaturate(60%), alpha(0.5)"`).
+        #[serde(default)]
+        disabled: Option<String>,
+    },
+
+    /// All the states are given.
+    States(Box<Button>),
+}
+
+derivable!(ButtonStyle);
+
+impl ButtonStyle {
+    /// Returns the states of the button.
+    /// The derived states are not defined, and are created by their fallback rules.
+    pub(crate) fn states(&self) -> Cow<'_, Button> {
+        match self {
+            ButtonStyle::Derived {
+                base,
+                hovered,
+                pressed,
+                disabled,
+            } => Cow::Owned(Button {
+                active: base.clone(),
+                hovered: ButtonComponent::None,
+                pressed: ButtonComponent::None,
+                disabled: ButtonComponent::None,
+                fallback: derived(&[
+                    ("hovered", hovered),
+                    ("pressed", pressed),
+                    ("disabled", disabled),
+                ]),
+            }),
+
+            ButtonStyle::States(button) => Cow::Borrowed(button.as_ref()),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -25,13 +96,31 @@ pub struct ButtonState {
     /// Key to the text color.
     pub text: String,
 
-    /// Key to the border theme.
-    pub border: String,
+    /// Key to the border theme, or inline border.
+    pub border: BorderValue,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(tag = "type")]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(remote = "Self", tag = "type")]
 pub enum ButtonComponent {
+    /// The button state is inherited from another theme, overriding some of its fields.
+    Extends {
+        /// Key to the parent button theme.
+        from: String,
+
+        /// Key to the background color.
+        #[serde(default)]
+        background: Option<String>,
+
+        /// Key to the text color.
+        #[serde(default)]
+        text: Option<String>,
+
+        /// Key to the border theme, or inline border.
+        #[serde(default)]
+        border: Option<BorderValue>,
+    },
+
     /// The theme is defined.
     Defined(ButtonState),
 
@@ -39,5 +128,130 @@ pub enum ButtonComponent {
     Inherited(String),
 
     /// The theme is not defined.
+    #[default]
     None,
 }
+
+compact!(ButtonComponent);
+
+impl ButtonComponent {
+    /// Returns the reference to the parent style and its overridden fields, if it is inherited.
+    pub(crate) fn parent(&self) -> Option<(&str, Vec<&'static str>)> {
+        match self {
+            ButtonComponent::Inherited(name) => Some((name, Vec::new())),
+
+            ButtonComponent::Extends {
+                from,
+                background,
+                text,
+                border,
+            } => Some((
+                from,
+                overrides(&[
+                    ("background", background.is_some()),
+                    ("text", text.is_some()),
+                    ("border", border.is_some()),
+                ]),
+            )),
+
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{serial, Color, Operation, Theme};
+
+    const RED: Color = Color(204, 51, 51, 1.0);
+
+    fn parse(styles: &str) -> Result<Theme, String> {
+        let theme: serial::Theme = ron::from_str(&format!(
+            r##"(
+                name: "test",
+                description: "",
+                application: (background_color: "white", text_color: "black"),
+                color: {{ "white": "#ffffff", "black": "#000000", "red": "#cc3333" }},
+                border: {{ "thin": (color: "black", radius: 1.0, width: 1.0) }},
+                {}
+            )"##,
+            styles
+        ))
+        .map_err(|error| error.to_string())?;
+
+        Theme::parse(&theme).map_err(|error| error.to_string())
+    }
+
+    fn apply(operations: &str, color: Color) -> Color {
+        Operation::apply_all(&Operation::parse_list(operations).unwrap(), color)
+    }
+
+    #[test]
+    fn derived_states() {
+        let theme = parse(
+            r#"
+            button: {
+                "primary": (
+                    base: (background: "red", text: "black", border: "thin"),
+                    hovered: Some("lighten(8%)"),
+                    pressed: Some("darken(6%)"),
+                    disabled: Some("desaturate(60%), alpha(0.5)"),
+                ),
+            },
+            "#,
+        )
+        .unwrap();
+
+        let [active, hovered, pressed, disabled] = theme.button["primary"].state;
+        assert_eq!(active.background, RED);
+        assert_eq!(hovered.background, apply("lighten(8%)", RED));
+        assert_eq!(pressed.background, apply("darken(6%)", RED));
+        assert_eq!(
+            disabled.background,
+            apply("desaturate(60%), alpha(0.5)", RED)
+        );
+    }
+
+    #[test]
+    fn category_fallback() {
+        let theme = parse(
+            r#"
+            fallback: { "button": { "pressed": (from: "hovered", apply: "alpha(0.5)") } },
+            button: {
+                "primary": (
+                    base: (background: "red", text: "black", border: "thin"),
+                    hovered: Some("lighten(8%)"),
+                ),
+                "plain": (base: (background: "red", text: "black", border: "thin")),
+            },
+            "#,
+        )
+        .unwrap();
+
+        let [_, hovered, pressed, disabled] = theme.button["primary"].state;
+        assert_eq!(pressed.background, apply("alpha(0.5)", hovered.background));
+        assert_eq!(disabled.background, apply("alpha(0.5)", RED));
+
+        let [active, hovered, _, _] = theme.button["plain"].state;
+        assert_eq!(hovered.background, active.background);
+    }
+
+    #[test]
+    fn state_errors() {
+        let error = parse(
+            r#"button: { "primary": (base: (background: "red", txt: "black", border: "thin")) }"#,
+        )
+        .unwrap_err();
+        assert!(error.contains("missing field `text`"), "{}", error);
+
+        let error = parse(
+            r#"button: { "primary": (active: (background: "red", text: 1, border: "thin")) }"#,
+        )
+        .unwrap_err();
+        assert!(
+            error.contains("found the unsigned integer `1`"),
+            "{}",
+            error
+        );
+    }
+}
@@ -161,14 +161,37 @@ fn color_f32_to_u8(value: f32) -> u8 {
     (value * 255.).round().clamp(0., 255.) as u8
 }
 
-impl From<&str> for Color {
-    fn from(value: &str) -> Self {
-        let srgb = value.parse::<css_color::Srgb>().unwrap();
+impl Color {
+    /// Attempts to parse a css color literal (e.g. `"#ff0000"` or `"rgba(255, 0, 0, 1.0)"`).
+    pub fn parse(value: &str) -> Result<Self, ()> {
+        let srgb = value.parse::<css_color::Srgb>().map_err(|_| ())?;
         let r = color_f32_to_u8(srgb.red);
         let g = color_f32_to_u8(srgb.green);
         let b = color_f32_to_u8(srgb.blue);
         let a = srgb.alpha;
-        Self(r, g, b, a)
+        Ok(Self(r, g, b, a))
+    }
+
+    /// Returns the css color literal of the color.
+    pub fn literal(&self) -> String {
+        format!("rgba({}, {}, {}, {})", self.0, self.1, self.2, self.3)
+    }
+}
+
+impl From<&str> for Color {
+    fn from(value: &str) -> Self {
+        Self::parse(value).unwrap()
+    }
+}
+
+impl From<iced::Color> for Color {
+    fn from(color: iced::Color) -> Self {
+        Self(
+            color_f32_to_u8(color.r),
+            color_f32_to_u8(color.g),
+            color_f32_to_u8(color.b),
+            color.a,
+        )
     }
 }
 
@@ -240,10 +263,7 @@ impl Serialize for Color {
     where
         S: serde::Serializer,
     {
-        serializer.collect_str(&format!(
-            "rgba({}, {}, {}, {})",
-            self.0, self.1, self.2, self.3
-        ))
+        serializer.collect_str(&self.literal())
     }
 }
 
@@ -268,13 +288,13 @@ impl<'de> Visitor<'de> for ColorVisitor {
     where
         E: serde::de::Error,
     {
-        Ok(Color::from(v.as_str()))
+        self.visit_str(&v)
     }
 
     fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
     where
         E: serde::de::Error,
     {
-        Ok(Color::from(v))
+        Color::parse(v).map_err(|_| E::custom(format!("invalid color \"{}\"", v)))
     }
 }
@@ -0,0 +1,442 @@
+//! Compact serial syntax of the style components.
+//!
+//! Besides their tagged form (e.g. `(type: "Defined", ...)`), the components accept a compact form:
+//! a string is inherited, an object with a `from` key is extended, any other object is defined
+//! and an omitted field or `null` is not defined. The compact form is turned into the tagged form
+//! before the component is deserialized, so the errors of its fields are reported as they are.
+
+use serde::de::{
+    self,
+    value::{MapDeserializer, SeqDeserializer},
+    Deserialize, Deserializer, IntoDeserializer, MapAccess, SeqAccess, Visitor,
+};
+
+use std::marker::PhantomData;
+
+/// Self-describing value, buffered while the form of a component is determined.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Content {
+    Bool(bool),
+    I64(i64),
+    U64(u64),
+    F64(f64),
+    String(String),
+    Unit,
+    None,
+    Some(Box<Content>),
+    Seq(Vec<Content>),
+    Map(Vec<(Content, Content)>),
+}
+
+impl Content {
+    /// Returns `true` if the value is the given string.
+    fn is(&self, string: &str) -> bool {
+        matches!(self, Content::String(s) if s == string)
+    }
+}
+
+struct ContentVisitor;
+
+impl<'de> Visitor<'de> for ContentVisitor {
+    type Value = Content;
+
+    fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str("any value")
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Content, E> {
+        Ok(Content::Bool(v))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Content, E> {
+        Ok(Content::I64(v))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Content, E> {
+        Ok(Content::U64(v))
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Content, E> {
+        Ok(Content::F64(v))
+    }
+
+    fn visit_char<E: de::Error>(self, v: char) -> Result<Content, E> {
+        Ok(Content::String(v.into()))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Content, E> {
+        Ok(Content::String(v.into()))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Content, E> {
+        Ok(Content::String(v))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Content, E> {
+        Ok(Content::Unit)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Content, E> {
+        Ok(Content::None)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Content, D::Error> {
+        Content::deserialize(deserializer).map(|v| Content::Some(Box::new(v)))
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(self, d: D) -> Result<Content, D::Error> {
+        Content::deserialize(d)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Content, A::Error> {
+        let mut values = Vec::new();
+
+        while let Some(value) = seq.next_element()? {
+            values.push(value);
+        }
+
+        Ok(Content::Seq(values))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Content, A::Error> {
+        let mut entries = Vec::new();
+
+        while let Some(entry) = map.next_entry()? {
+            entries.push(entry);
+        }
+
+        Ok(Content::Map(entries))
+    }
+}
+
+impl<'de> Deserialize<'de> for Content {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ContentVisitor)
+    }
+}
+
+/// Deserializer of a buffered value.
+pub(crate) struct ContentDeserializer<E> {
+    content: Content,
+    error: PhantomData<E>,
+}
+
+impl<'de, E: de::Error> IntoDeserializer<'de, E> for Content {
+    type Deserializer = ContentDeserializer<E>;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        ContentDeserializer {
+            content: self,
+            error: PhantomData,
+        }
+    }
+}
+
+impl<'de, E: de::Error> Deserializer<'de> for ContentDeserializer<E> {
+    type Error = E;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        match self.content {
+            Content::Bool(v) => visitor.visit_bool(v),
+            Content::I64(v) => visitor.visit_i64(v),
+            Content::U64(v) => visitor.visit_u64(v),
+            Content::F64(v) => visitor.visit_f64(v),
+            Content::String(v) => visitor.visit_string(v),
+            Content::Unit => visitor.visit_unit(),
+            Content::None => visitor.visit_none(),
+            Content::Some(v) => visitor.visit_some(v.into_deserializer()),
+            Content::Seq(v) => {
+                let mut seq = SeqDeserializer::new(v.into_iter());
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                Ok(value)
+            }
+            Content::Map(v) => {
+                let mut map = MapDeserializer::new(v.into_iter());
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(value)
+            }
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        match self.content {
+            Content::Unit | Content::None => visitor.visit_none(),
+            Content::Some(v) => visitor.visit_some(v.into_deserializer()),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, E> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, E> {
+        match self.content {
+            Content::String(variant) => visitor.visit_enum(variant.into_deserializer()),
+            _ => self
+                .deserialize_any(visitor)
+                .map_err(|_| E::custom(format!("expected a variant of {}: {:?}", name, variants))),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// Adds the `type` key to the entries of an object in compact form, naming the `present`
+/// variant if the object has the given key and the `absent` variant otherwise.
+fn tag(entries: &mut Vec<(Content, Content)>, key: &str, present: &str, absent: &str) {
+    if !entries.iter().any(|(k, _)| k.is("type")) {
+        let tag = match entries.iter().any(|(k, _)| k.is(key)) {
+            true => present,
+            _ => absent,
+        };
+
+        entries.push((Content::String("type".into()), Content::String(tag.into())));
+    }
+}
+
+/// Deserializes an object in compact or tagged form, returning its tagged form.
+/// The compact form names the `present` variant if it has the given key.
+pub(crate) fn tagged<'de, D: Deserializer<'de>>(
+    deserializer: D,
+    key: &str,
+    present: &str,
+    absent: &str,
+) -> Result<Content, D::Error> {
+    match Content::deserialize(deserializer)? {
+        Content::Map(mut entries) => {
+            tag(&mut entries, key, present, absent);
+            Ok(Content::Map(entries))
+        }
+
+        _ => Err(de::Error::custom("expected an object")),
+    }
+}
+
+/// Serial form of a component, before it is deserialized.
+pub(crate) enum Compact {
+    /// The compact inherited form.
+    Inherited(String),
+
+    /// The tagged form, with the tag of the compact forms added.
+    Tagged(Content),
+}
+
+struct CompactVisitor;
+
+impl<'de> Visitor<'de> for CompactVisitor {
+    type Value = Compact;
+
+    fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str("a style key, an object or null")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Compact, E> {
+        Ok(Compact::Inherited(v.into()))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Compact, E> {
+        Ok(Compact::Inherited(v))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Compact, E> {
+        let tag = (
+            Content::String("type".into()),
+            Content::String("None".into()),
+        );
+        Ok(Compact::Tagged(Content::Map(vec![tag])))
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Compact, E> {
+        self.visit_unit()
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Compact, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Compact, A::Error> {
+        let mut entries = match ContentVisitor.visit_map(map)? {
+            Content::Map(entries) => entries,
+            _ => unreachable!(),
+        };
+
+        tag(&mut entries, "from", "Extends", "Defined");
+        Ok(Compact::Tagged(Content::Map(entries)))
+    }
+}
+
+impl<'de> Deserialize<'de> for Compact {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(CompactVisitor)
+    }
+}
+
+/// Implements the compact serial syntax for a component.
+/// The component derives its tagged form with `#[serde(remote = "Self", tag = "type")]`, and
+/// must have the `Defined` and `Inherited` variants.
+macro_rules! compact {
+    ($component:ident) => {
+        impl<'de> serde::Deserialize<'de> for $component {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                use serde::de::IntoDeserializer;
+
+                match $crate::component::Compact::deserialize(deserializer)? {
+                    $crate::component::Compact::Inherited(name) => Ok(Self::Inherited(name)),
+                    $crate::component::Compact::Tagged(content) => {
+                        Self::deserialize(content.into_deserializer())
+                    }
+                }
+            }
+        }
+
+        impl serde::Serialize for $component {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                match self {
+                    Self::Inherited(name) => serializer.serialize_str(name),
+                    Self::Defined(state) => state.serialize(serializer),
+                    _ => Self::serialize(self, serializer),
+                }
+            }
+        }
+    };
+}
+
+/// Implements the serial syntax for a style whose states can be derived from a base state.
+/// The style derives its tagged form with `#[serde(remote = "Self", tag = "type")]`, and must have
+/// the `Derived` and `States` variants. An object with a `base` key is derived.
+macro_rules! derivable {
+    ($style:ident) => {
+        impl<'de> serde::Deserialize<'de> for $style {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                use serde::de::IntoDeserializer;
+
+                let content = $crate::component::tagged(deserializer, "base", "Derived", "States")?;
+                Self::deserialize(content.into_deserializer())
+            }
+        }
+
+        impl serde::Serialize for $style {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                match self {
+                    Self::States(states) => states.serialize(serializer),
+                    _ => Self::serialize(self, serializer),
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use {compact, derivable};
+
+#[cfg(test)]
+mod tests {
+    use crate::serial::{BorderValue, ButtonComponent};
+
+    fn parse(component: &str) -> Result<ButtonComponent, String> {
+        ron::from_str(component).map_err(|error| error.to_string())
+    }
+
+    #[test]
+    fn compact_form() {
+        let defined = parse(r#"(background: "red", text: "white", border: "thin")"#).unwrap();
+        assert!(matches!(
+            defined,
+            ButtonComponent::Defined(state) if state.background == "red"
+                && matches!(&state.border, BorderValue::Key(key) if key == "thin")
+        ));
+
+        let extends = parse(r#"(from: "primary", text: Some("black"))"#).unwrap();
+        assert!(matches!(
+            extends,
+            ButtonComponent::Extends { from, text: Some(text), background: None, .. }
+                if from == "primary" && text == "black"
+        ));
+
+        let inherited = parse(r#""primary""#).unwrap();
+        assert!(matches!(inherited, ButtonComponent::Inherited(name) if name == "primary"));
+    }
+
+    #[test]
+    fn tagged_form() {
+        let defined =
+            parse(r#"(type: "Defined", background: "red", text: "white", border: "thin")"#);
+        assert!(matches!(defined, Ok(ButtonComponent::Defined(_))));
+
+        let extends = parse(r#"(type: "Extends", from: "primary")"#);
+        assert!(matches!(extends, Ok(ButtonComponent::Extends { .. })));
+
+        let none = parse(r#"(type: "None")"#);
+        assert!(matches!(none, Ok(ButtonComponent::None)));
+    }
+
+    #[test]
+    fn null() {
+        assert!(matches!(parse("()"), Ok(ButtonComponent::None)));
+        assert!(matches!(parse("None"), Ok(ButtonComponent::None)));
+    }
+
+    #[test]
+    fn mismatched_type() {
+        let error = parse(r#"(type: "Inherited", background: "red")"#).unwrap_err();
+        assert!(
+            error.contains("Expected a string but found a map"),
+            "{}",
+            error
+        );
+
+        let error = parse(r#"(type: "Extends", background: "red")"#).unwrap_err();
+        assert!(error.contains("missing field `from`"), "{}", error);
+
+        let error = parse(r#"(type: "Derived", background: "red")"#).unwrap_err();
+        assert!(error.contains("enum variant named `Derived`"), "{}", error);
+    }
+
+    #[test]
+    fn field_errors() {
+        let error = parse(r#"(background: "red", txt: "white", border: "thin")"#).unwrap_err();
+        assert!(error.contains("missing field `text`"), "{}", error);
+
+        let error = parse(r#"(background: "red", text: 1, border: "thin")"#).unwrap_err();
+        assert!(
+            error.contains("found the unsigned integer `1`"),
+            "{}",
+            error
+        );
+
+        let error = parse("[1]").unwrap_err();
+        assert!(error.contains("found a sequence"), "{}", error);
+    }
+
+    #[test]
+    fn round_trip() {
+        for component in [
+            r#"(background: "red", text: "white", border: "thin")"#,
+            r#"(from: "primary", text: Some("black"))"#,
+            r#""primary""#,
+            "()",
+        ] {
+            let parsed = parse(component).unwrap();
+            let serialized = ron::to_string(&parsed).unwrap();
+
+            assert_eq!(
+                format!("{:?}", parse(&serialized).unwrap()),
+                format!("{:?}", parsed)
+            );
+        }
+    }
+}
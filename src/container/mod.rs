@@ -2,10 +2,15 @@
 
 pub mod serial;
 
-use crate::{Border, Color, Theme};
+use crate::{
+    theme::cross::{self, split, state, Shared},
+    Border, Color, Theme,
+};
 
 use iced::widget::container::{Appearance, StyleSheet};
 
+use serial::ContainerComponent;
+
 #[derive(Clone, Copy, Debug)]
 pub struct Container {
     /// Background of the container.
@@ -13,24 +18,102 @@ pub struct Container {
 
     /// Border of the container.
     pub border: Border,
+
+    /// Text color of the container.
+    /// If not defined, the text color is inherited.
+    pub text: Option<Color>,
 }
 
 impl Container {
     /// Attempts to create a theme from its &serialized version.
-    pub fn create(serial: &serial::Container, theme: &Theme) -> Result<Self, ()> {
-        // Get the color of the container.
-        let color = match theme.color.get(serial.color.as_str()) {
-            Some(color) => *color,
-            _ => return Err(()),
+    pub fn create(serial: &ContainerComponent, theme: &Theme) -> Result<Self, ()> {
+        // Inherit the shared fields of a style of another category.
+        if let Some((reference, _)) = serial.parent() {
+            if let (Some(category), name) = split(reference, "container") {
+                let shared = theme.shared(category, name, state("container", 0))?;
+                return Self::cross(serial, shared, theme);
+            }
+        }
+
+        match serial {
+            ContainerComponent::Defined(container) => Self::from(container, theme),
+
+            ContainerComponent::Inherited(name) => {
+                match theme.inherited("container", &theme.container, name) {
+                    Some(container) => Ok(*container),
+                    _ => Err(()),
+                }
+            }
+
+            ContainerComponent::Extends {
+                from,
+                color,
+                border,
+                text,
+            } => match theme.inherited("container", &theme.container, from) {
+                Some(container) => {
+                    let mut container = *container;
+
+                    if let Some(key) = color {
+                        container.color = theme.color_key(key)?;
+                    }
+
+                    if let Some(key) = border {
+                        container.border = theme.border_value(key)?;
+                    }
+
+                    if let Some(key) = text {
+                        container.text = Some(theme.color_key(key)?);
+                    }
+
+                    Ok(container)
+                }
+                _ => Err(()),
+            },
+        }
+    }
+
+    /// Creates a style from the shared fields of a style of another category.
+    fn cross(serial: &ContainerComponent, shared: Shared, theme: &Theme) -> Result<Container, ()> {
+        let (color, border, text) = match serial {
+            ContainerComponent::Extends {
+                color,
+                border,
+                text,
+                ..
+            } => (color, border, text),
+            _ => (&None, &None, &None),
         };
 
+        Ok(Container {
+            color: cross::color(theme, color, shared.background)?,
+            border: cross::border(theme, border, shared.border)?,
+            text: match text {
+                Some(key) => Some(theme.color_key(key)?),
+                _ => shared.text,
+            },
+        })
+    }
+
+    /// Attempts to create a theme from its &serialized definition.
+    fn from(serial: &serial::Container, theme: &Theme) -> Result<Self, ()> {
+        // Get the color of the container.
+        let color = theme.color_key(&serial.color)?;
+
         // Get the border of the container.
-        let border = match theme.border.get(serial.border.as_str()) {
-            Some(border) => *border,
-            _ => return Err(()),
+        let border = theme.border_value(&serial.border)?;
+
+        // Get the text color of the container.
+        let text = match &serial.text {
+            Some(key) => Some(theme.color_key(key)?),
+            _ => None,
         };
 
-        Ok(Container { color, border })
+        Ok(Container {
+            color,
+            border,
+            text,
+        })
     }
 }
 
@@ -44,12 +127,18 @@ impl StyleSheet for Container {
     type Style = iced::Theme;
 
     fn appearance(&self, _: &Self::Style) -> Appearance {
+        (*self).into()
+    }
+}
+
+impl From<Container> for Appearance {
+    fn from(container: Container) -> Self {
         Appearance {
-            text_color: None,
-            background: Some(self.color.into()),
-            border_radius: self.border.radius,
-            border_width: self.border.width,
-            border_color: self.border.color.into(),
+            text_color: container.text.map(|c| c.into()),
+            background: Some(container.color.into()),
+            border_radius: container.border.radius,
+            border_width: container.border.width,
+            border_color: container.border.color.into(),
         }
     }
 }
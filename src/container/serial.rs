@@ -1,5 +1,7 @@
 //! `serde` compatible version of the container theme.
 
+use crate::{component::compact, serial::BorderValue, theme::cross::overrides};
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -7,6 +9,65 @@ pub struct Container {
     /// Key to the background color.
     pub color: String,
 
-    /// Key to the border definition.
-    pub border: String,
+    /// Key to the border definition, or inline border.
+    pub border: BorderValue,
+
+    /// Key to the text color.
+    #[serde(default)]
+    pub text: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(remote = "Self", tag = "type")]
+pub enum ContainerComponent {
+    /// The container is inherited from another container theme, overriding some of its fields.
+    Extends {
+        /// Key to the parent container theme.
+        from: String,
+
+        /// Key to the background color.
+        #[serde(default)]
+        color: Option<String>,
+
+        /// Key to the border definition, or inline border.
+        #[serde(default)]
+        border: Option<BorderValue>,
+
+        /// Key to the text color.
+        #[serde(default)]
+        text: Option<String>,
+    },
+
+    /// The container is defined.
+    Defined(Container),
+
+    /// The container is inherited from another container theme.
+    Inherited(String),
+}
+
+compact!(ContainerComponent);
+
+impl ContainerComponent {
+    /// Returns the reference to the parent style and its overridden fields, if it is inherited.
+    pub(crate) fn parent(&self) -> Option<(&str, Vec<&'static str>)> {
+        match self {
+            ContainerComponent::Inherited(name) => Some((name, Vec::new())),
+
+            ContainerComponent::Extends {
+                from,
+                color,
+                border,
+                text,
+            } => Some((
+                from,
+                overrides(&[
+                    ("color", color.is_some()),
+                    ("border", border.is_some()),
+                    ("text", text.is_some()),
+                ]),
+            )),
+
+            ContainerComponent::Defined(_) => None,
+        }
+    }
 }
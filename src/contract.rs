@@ -0,0 +1,181 @@
+//! Style contracts, which declare the style keys an application requires.
+//!
+//! Checking a theme against the contract of the application when the theme is loaded reports the
+//! missing styles, instead of failing when a widget gets a style that is not defined.
+
+use crate::{theme::cascade, Error, Theme};
+
+use serde::{Deserialize, Serialize};
+
+use std::collections::HashMap;
+
+/// Style keys required by an application, in each style category.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct StyleContract {
+    /// Required border keys.
+    pub border: Vec<String>,
+
+    /// Required button keys.
+    pub button: Vec<String>,
+
+    /// Required container keys.
+    pub container: Vec<String>,
+
+    /// Required pane grid keys.
+    pub panegrid: Vec<String>,
+
+    /// Required picklist keys.
+    pub picklist: Vec<String>,
+
+    /// Required progress bar keys.
+    pub progressbar: Vec<String>,
+
+    /// Required scrollable keys.
+    pub scrollable: Vec<String>,
+
+    /// Required text keys.
+    pub text: Vec<String>,
+
+    /// Required text input keys.
+    pub textinput: Vec<String>,
+
+    /// Required tooltip keys.
+    pub tooltip: Vec<String>,
+}
+
+/// Set of style keys of an application, usually an enum that derives it.
+/// Each variant of the enum declares its category and key with a `style` attribute.
+///
+/// ```ignore
+/// #[derive(StyleKeys)]
+/// enum AppStyle {
+///     #[style(button = "primary")]
+///     Primary,
+///
+///     #[style(textinput = "search")]
+///     Search,
+/// }
+///
+/// theme.check_contract(&AppStyle::contract())?;
+///
+/// button("Ok").style(AppStyle::Primary.key().to_string());
+/// ```
+pub trait StyleKeys {
+    /// Returns the contract that requires all the keys of the set.
+    fn contract() -> StyleContract;
+
+    /// Returns the style key.
+    fn key(&self) -> &'static str;
+
+    /// Returns the style category of the key.
+    fn category(&self) -> &'static str;
+}
+
+/// Adds an error for each required key of a category that the theme does not define.
+/// Dotted keys are defined if any of their levels is defined.
+fn check<T>(
+    category: &'static str,
+    styles: &HashMap<String, T>,
+    required: &[String],
+    missing: &mut Vec<Error>,
+) {
+    for name in required {
+        if cascade(styles, name).is_none() {
+            missing.push(Error::Missing {
+                category,
+                name: name.clone(),
+            });
+        }
+    }
+}
+
+impl Theme {
+    /// Checks that this theme defines all the styles required by a contract.
+    /// Returns an error for each missing style.
+    pub fn check_contract(&self, contract: &StyleContract) -> Result<(), Vec<Error>> {
+        let mut missing = Vec::new();
+        let m = &mut missing;
+
+        check("border", &self.border, &contract.border, m);
+        check("button", &self.button, &contract.button, m);
+        check("container", &self.container, &contract.container, m);
+        check("panegrid", &self.panegrid, &contract.panegrid, m);
+        check("picklist", &self.picklist, &contract.picklist, m);
+        check("progressbar", &self.progressbar, &contract.progressbar, m);
+        check("scrollable", &self.scrollable, &contract.scrollable, m);
+        check("text", &self.text, &contract.text, m);
+        check("textinput", &self.textinput, &contract.textinput, m);
+        check("tooltip", &self.tooltip, &contract.tooltip, m);
+
+        match missing.is_empty() {
+            true => Ok(()),
+            _ => Err(missing),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{serial, StyleKeys};
+
+    #[derive(StyleKeys)]
+    enum AppStyle {
+        #[style(button = "primary")]
+        Primary,
+
+        #[style(button = "primary.small")]
+        Small,
+
+        #[style(textinput = "search")]
+        Search,
+    }
+
+    fn theme() -> Theme {
+        let theme: serial::Theme = ron::from_str(
+            r##"(
+                name: "test",
+                description: "",
+                application: (background_color: "white", text_color: "black"),
+                color: { "white": "#ffffff", "black": "#000000" },
+                border: { "thin": (color: "black", radius: 1.0, width: 1.0) },
+                button: {
+                    "primary": (active: (background: "white", text: "black", border: "thin")),
+                },
+            )"##,
+        )
+        .unwrap();
+
+        Theme::parse(&theme).unwrap()
+    }
+
+    #[test]
+    fn derived_keys() {
+        assert_eq!(AppStyle::Primary.category(), "button");
+        assert_eq!(AppStyle::Small.key(), "primary.small");
+        assert_eq!(AppStyle::Search.category(), "textinput");
+
+        assert_eq!(
+            AppStyle::contract(),
+            StyleContract {
+                button: vec!["primary".into(), "primary.small".into()],
+                textinput: vec!["search".into()],
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn missing_styles() {
+        let missing = theme().check_contract(&AppStyle::contract()).unwrap_err();
+
+        assert_eq!(
+            missing,
+            vec![Error::Missing {
+                category: "textinput",
+                name: "search".into(),
+            }]
+        );
+    }
+}
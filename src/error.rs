@@ -0,0 +1,293 @@
+//! Errors of the theme parsing.
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// A style could not be created, due to missing keys or circular inheritance.
+    Unresolved {
+        /// Category of the style.
+        category: &'static str,
+
+        /// Name of the style.
+        name: String,
+    },
+
+    /// A style references a category that does not exist.
+    UnknownCategory {
+        /// Category of the style.
+        category: &'static str,
+
+        /// Name of the style.
+        name: String,
+
+        /// Qualified reference to the parent style.
+        reference: String,
+    },
+
+    /// A style inherits from a style of another category that does not provide one of its fields.
+    Incompatible {
+        /// Category of the style.
+        category: &'static str,
+
+        /// Name of the style.
+        name: String,
+
+        /// Qualified reference to the parent style.
+        reference: String,
+
+        /// Field that is not provided by the parent style.
+        field: &'static str,
+    },
+
+    /// A style required by a contract is not defined.
+    Missing {
+        /// Category of the style.
+        category: &'static str,
+
+        /// Name of the style.
+        name: String,
+    },
+
+    /// A color operation could not be parsed.
+    InvalidOperation(String),
+
+    /// A fallback rule references a state that the category does not have.
+    UnknownState {
+        /// Category of the fallback rule.
+        category: String,
+
+        /// Name of the state.
+        state: String,
+    },
+
+    /// An entry of the theme could not be created.
+    Entry {
+        /// Category of the entry.
+        category: &'static str,
+
+        /// Name of the entry.
+        name: String,
+
+        /// Error of the entry.
+        error: Box<Error>,
+    },
+
+    /// A field of a section of the theme references a key that is not defined.
+    UnknownKey {
+        /// Section of the theme (e.g. `"palette"`).
+        section: &'static str,
+
+        /// Field that references the key.
+        field: &'static str,
+
+        /// Key that is not defined.
+        key: String,
+    },
+
+    /// A default style of the application is not defined.
+    MissingDefault {
+        /// Widget that uses the default style (e.g. `"checkbox"`).
+        widget: &'static str,
+
+        /// Category of the default style (e.g. `"button"` for a checkbox).
+        category: &'static str,
+
+        /// Key of the default style.
+        key: String,
+    },
+
+    /// A template was instantiated with a parameter that it does not declare.
+    UnknownParameter(String),
+
+    /// A template parameter without a default value was not given.
+    MissingParameter(String),
+
+    /// A template parameter was given a value of a different type.
+    MismatchedParameter(String),
+
+    /// An included fragment could not be loaded.
+    Include {
+        /// Path of the fragment.
+        file: String,
+
+        /// Reason why the fragment could not be loaded.
+        reason: String,
+    },
+
+    /// An entry is defined more than once in the main theme and its fragments.
+    Conflict {
+        /// Category of the entry.
+        category: &'static str,
+
+        /// Name of the entry.
+        name: String,
+
+        /// Path of the fragment that defines the entry again.
+        file: String,
+
+        /// Path of the fragment that defines the entry first, or `None` for the main theme.
+        previous: Option<String>,
+    },
+
+    /// A variant that is not declared by the theme was selected.
+    UnknownVariant(String),
+
+    /// A scope could not be created from the entries it overrides.
+    Scope {
+        /// Name of the scope.
+        scope: String,
+
+        /// Error of the scope.
+        error: Box<Error>,
+    },
+
+    /// An entry registered under a namespace could not be created.
+    Namespace {
+        /// Name of the namespace.
+        namespace: String,
+
+        /// Error of the entry.
+        error: Box<Error>,
+    },
+
+    /// An entry defined in an included fragment could not be created.
+    File {
+        /// Path of the fragment.
+        file: String,
+
+        /// Error of the entry.
+        error: Box<Error>,
+    },
+}
+
+impl Error {
+    /// Returns the category and name of the entry that caused the error, if any.
+    pub fn entry(&self) -> Option<(&'static str, &str)> {
+        match self {
+            Error::Unresolved { category, name }
+            | Error::Missing { category, name }
+            | Error::UnknownCategory { category, name, .. }
+            | Error::Incompatible { category, name, .. }
+            | Error::Entry { category, name, .. }
+            | Error::Conflict { category, name, .. } => Some((category, name)),
+
+            Error::Scope { error, .. }
+            | Error::Namespace { error, .. }
+            | Error::File { error, .. } => error.entry(),
+
+            _ => None,
+        }
+    }
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Error::Unresolved { category, name } => write!(
+                f,
+                "{} \"{}\" could not be created (missing keys or circular inheritance)",
+                category, name
+            ),
+
+            Error::UnknownCategory {
+                category,
+                name,
+                reference,
+            } => write!(
+                f,
+                "{} \"{}\" inherits from \"{}\", which is not a known category",
+                category, name, reference
+            ),
+
+            Error::Incompatible {
+                category,
+                name,
+                reference,
+                field,
+            } => write!(
+                f,
+                "{} \"{}\" inherits from \"{}\", which does not provide the field \"{}\"",
+                category, name, reference, field
+            ),
+
+            Error::Missing { category, name } => {
+                write!(f, "{} \"{}\" is required but not defined", category, name)
+            }
+
+            Error::InvalidOperation(operation) => {
+                write!(f, "\"{}\" is not a valid color operation", operation)
+            }
+
+            Error::UnknownState { category, state } => {
+                write!(f, "{} does not have a \"{}\" state", category, state)
+            }
+
+            Error::Entry {
+                category,
+                name,
+                error,
+            } => write!(f, "{} \"{}\": {}", category, name, error),
+
+            Error::UnknownKey {
+                section,
+                field,
+                key,
+            } => write!(
+                f,
+                "{} field \"{}\" references \"{}\", which is not defined",
+                section, field, key
+            ),
+
+            Error::MissingDefault {
+                widget,
+                category,
+                key,
+            } => write!(
+                f,
+                "the default {} style \"{}\" is not a defined {} style",
+                widget, key, category
+            ),
+
+            Error::UnknownParameter(name) => write!(f, "parameter \"{}\" is not declared", name),
+
+            Error::MissingParameter(name) => write!(f, "parameter \"{}\" is not given", name),
+
+            Error::MismatchedParameter(name) => {
+                write!(f, "parameter \"{}\" is given a value of a different type", name)
+            }
+
+            Error::Include { file, reason } => {
+                write!(f, "\"{}\" could not be included: {}", file, reason)
+            }
+
+            Error::Conflict {
+                category,
+                name,
+                file,
+                previous,
+            } => match previous {
+                Some(previous) => write!(
+                    f,
+                    "{} \"{}\" in \"{}\" is already defined in \"{}\"",
+                    category, name, file, previous
+                ),
+                _ => write!(
+                    f,
+                    "{} \"{}\" in \"{}\" is already defined in the main theme",
+                    category, name, file
+                ),
+            },
+
+            Error::UnknownVariant(name) => write!(f, "variant \"{}\" is not declared", name),
+
+            Error::Scope { scope, error } => write!(f, "in scope \"{}\": {}", scope, error),
+
+            Error::Namespace { namespace, error } => {
+                write!(f, "in namespace \"{}\": {}", namespace, error)
+            }
+
+            Error::File { file, error } => write!(f, "in \"{}\": {}", file, error),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
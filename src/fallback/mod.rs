@@ -0,0 +1,317 @@
+//! Fallback rules of the states that are not defined.
+//! A fallback rule copies another state of the same style, applying some color operations
+//! (e.g. the disabled state is the active state with half its opacity).
+
+pub mod serial;
+
+use crate::{
+    operation::{Operation, Transform},
+    theme::cross,
+    Error, Theme,
+};
+
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fallback {
+    /// Name of the state that is copied.
+    pub from: String,
+
+    /// Color operations applied to the copied state.
+    pub operations: Vec<Operation>,
+}
+
+impl Fallback {
+    /// Attempts to create a fallback rule of a category from its &serialized version.
+    /// The copied state must be a state of the category.
+    pub fn create(serial: &serial::Fallback, category: &str) -> Result<Self, Error> {
+        if cross::index(category, &serial.from).is_none() {
+            return Err(Error::UnknownState {
+                category: category.into(),
+                state: serial.from.clone(),
+            });
+        }
+
+        Ok(Fallback {
+            from: serial.from.clone(),
+            operations: Operation::parse_list(&serial.apply)?,
+        })
+    }
+
+    /// Default fallback rule of a state of a category.
+    fn default(category: &str, state: &str) -> Option<Self> {
+        let (from, operations) = match (category, state) {
+            ("button", "hovered") => ("active", vec![]),
+            ("button", "pressed") => ("hovered", vec![]),
+            ("button", "disabled") => ("active", vec![Operation::Alpha(0.5)]),
+
+            ("panegrid", "hovered") => ("picked", vec![]),
+
+            ("picklist", "hovered") => ("active", vec![]),
+
+            ("scrollable", "hovered") => ("active", vec![]),
+            ("scrollable", "dragging") => ("hovered", vec![]),
+
+            ("textinput", "hovered") => ("active", vec![]),
+            ("textinput", "focused") => ("hovered", vec![]),
+
+            _ => return None,
+        };
+
+        Some(Fallback {
+            from: from.into(),
+            operations,
+        })
+    }
+}
+
+/// Attempts to create the fallback rules of a style, mapping the names of the states to their rules.
+pub(crate) fn create(
+    serial: &HashMap<String, serial::Fallback>,
+    category: &str,
+) -> Result<HashMap<String, Fallback>, Error> {
+    let mut rules = HashMap::new();
+
+    for (state, rule) in serial {
+        if cross::index(category, state).is_none() {
+            return Err(Error::UnknownState {
+                category: category.into(),
+                state: state.clone(),
+            });
+        }
+
+        rules.insert(state.clone(), Fallback::create(rule, category)?);
+    }
+
+    Ok(rules)
+}
+
+/// Checks the fallback rules of a style, reporting the error with the style that defines them.
+fn check(
+    category: &'static str,
+    name: &str,
+    serial: &HashMap<String, serial::Fallback>,
+) -> Result<(), Error> {
+    create(serial, category)
+        .map(|_| ())
+        .map_err(|error| Error::Entry {
+            category,
+            name: name.into(),
+            error: Box::new(error),
+        })
+}
+
+/// Checks the fallback rules of all the styles of a theme before creating them.
+pub(crate) fn validate(theme: &crate::serial::Theme) -> Result<(), Error> {
+    for (name, button) in &theme.button {
+        check("button", name, &button.states().fallback)?;
+    }
+
+    for (name, panegrid) in &theme.panegrid {
+        check("panegrid", name, &panegrid.fallback)?;
+    }
+
+    for (name, picklist) in &theme.picklist {
+        check("picklist", name, &picklist.states().fallback)?;
+    }
+
+    for (name, scrollable) in &theme.scrollable {
+        check("scrollable", name, &scrollable.fallback)?;
+    }
+
+    for (name, textinput) in &theme.textinput {
+        check("textinput", name, &textinput.states().fallback)?;
+    }
+
+    Ok(())
+}
+
+/// Fills the states that are not defined with their fallback rules.
+///
+/// The rules of the style take precedence over the rules of the theme, which take precedence over
+/// the default rules of the category. The states that have no rule copy the first defined state.
+pub(crate) fn resolve<S: Transform + Copy, const N: usize>(
+    category: &str,
+    states: [Option<S>; N],
+    rules: &HashMap<String, Fallback>,
+    theme: &Theme,
+) -> Result<[S; N], ()> {
+    // Find the first state that is defined.
+    let default = states.iter().flatten().next().copied().ok_or(())?;
+
+    let rules: Vec<Option<Fallback>> = (0..N)
+        .map(|i| {
+            let state = cross::state(category, i);
+
+            match rules.get(state) {
+                Some(rule) => Some(rule.clone()),
+                _ => match theme
+                    .fallback
+                    .get(category)
+                    .and_then(|rules| rules.get(state))
+                {
+                    Some(rule) => Some(rule.clone()),
+                    _ => Fallback::default(category, state),
+                },
+            }
+        })
+        .collect();
+
+    let mut resolved = states;
+
+    loop {
+        // Apply the rules whose copied state is resolved, until no rule can be applied.
+        let mut changed = false;
+
+        for i in 0..N {
+            let state = match (&resolved[i], &rules[i]) {
+                (None, Some(rule)) => cross::index(category, &rule.from)
+                    .and_then(|j| resolved[j])
+                    .map(|state| state.apply(&rule.operations)),
+                _ => None,
+            };
+
+            if state.is_some() {
+                resolved[i] = state;
+                changed = true;
+            }
+        }
+
+        if changed {
+            continue;
+        }
+
+        // Copy the first defined state into a state without rules (or with circular rules),
+        // which may allow other rules to be applied.
+        let mut unresolved = (0..N).filter(|i| resolved[*i].is_none());
+
+        match unresolved
+            .clone()
+            .find(|i| rules[*i].is_none())
+            .or(unresolved.next())
+        {
+            Some(i) => resolved[i] = Some(default),
+            _ => break,
+        }
+    }
+
+    Ok(resolved.map(|state| state.unwrap_or(default)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Border, Color};
+
+    fn border(r: u8) -> Border {
+        Border {
+            color: Color(r, 0, 0, 1.0),
+            radius: 0.0,
+            width: 1.0,
+        }
+    }
+
+    fn rule(from: &str, operations: Vec<Operation>) -> Fallback {
+        Fallback {
+            from: from.into(),
+            operations,
+        }
+    }
+
+    #[test]
+    fn default_rules() {
+        let theme = Theme::new();
+        let states = [Some(border(100)), None, None, None];
+
+        let [active, hovered, pressed, disabled] =
+            resolve("button", states, &HashMap::new(), &theme).unwrap();
+
+        assert_eq!(hovered, active);
+        assert_eq!(pressed, active);
+        assert_eq!(disabled.color, Color(100, 0, 0, 0.5));
+    }
+
+    #[test]
+    fn chained_rules() {
+        let theme = Theme::new();
+        let states = [Some(border(100)), None, None, None];
+
+        let rules = HashMap::from([
+            ("pressed".to_string(), rule("hovered", vec![Operation::Alpha(0.5)])),
+            ("hovered".to_string(), rule("active", vec![Operation::Alpha(0.5)])),
+        ]);
+
+        let [_, hovered, pressed, _] = resolve("button", states, &rules, &theme).unwrap();
+
+        assert_eq!(hovered.color, Color(100, 0, 0, 0.5));
+        assert_eq!(pressed.color, Color(100, 0, 0, 0.25));
+    }
+
+    #[test]
+    fn circular_rules() {
+        let theme = Theme::new();
+        let states = [Some(border(100)), None, Some(border(50)), None];
+
+        let rules = HashMap::from([
+            ("hovered".to_string(), rule("disabled", vec![Operation::Alpha(0.5)])),
+            ("disabled".to_string(), rule("hovered", vec![Operation::Alpha(0.5)])),
+        ]);
+
+        let [active, hovered, pressed, disabled] =
+            resolve("button", states, &rules, &theme).unwrap();
+
+        // The cycle is broken by copying the first defined state into one of its states.
+        assert_eq!(active, border(100));
+        assert_eq!(pressed, border(50));
+        assert!(hovered == active || disabled == active);
+        assert!(hovered.color.3 == 0.5 || disabled.color.3 == 0.5);
+    }
+
+    #[test]
+    fn undefined_states() {
+        let theme = Theme::new();
+        let states: [Option<Border>; 4] = [None; 4];
+
+        assert!(resolve("button", states, &HashMap::new(), &theme).is_err());
+    }
+
+    #[test]
+    fn theme_rules() {
+        let mut theme = Theme::new();
+        let states = [Some(border(100)), None, None, None];
+
+        let rules = HashMap::from([("hovered".to_string(), rule("active", vec![]))]);
+        let theme_rules = HashMap::from([(
+            "hovered".to_string(),
+            rule("active", vec![Operation::Alpha(0.5)]),
+        )]);
+
+        theme.fallback.insert("button".into(), theme_rules);
+
+        // The rules of the style take precedence over the rules of the theme.
+        let [active, hovered, ..] = resolve("button", states, &rules, &theme).unwrap();
+        assert_eq!(hovered, active);
+
+        let [_, hovered, ..] = resolve("button", states, &HashMap::new(), &theme).unwrap();
+        assert_eq!(hovered.color, Color(100, 0, 0, 0.5));
+    }
+
+    #[test]
+    fn unknown_states() {
+        let serial = HashMap::from([(
+            "hovered".to_string(),
+            serial::Fallback {
+                from: "focused".into(),
+                apply: String::new(),
+            },
+        )]);
+
+        assert_eq!(
+            create(&serial, "button"),
+            Err(Error::UnknownState {
+                category: "button".into(),
+                state: "focused".into(),
+            })
+        );
+    }
+}
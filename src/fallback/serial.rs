@@ -0,0 +1,34 @@
+//! `serde` compatible version of the fallback rules.
+
+use serde::{Deserialize, Serialize};
+
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Fallback {
+    /// Name of the state that is copied (e.g. `"active"`).
+    pub from: String,
+
+    /// Comma separated list of color operations applied to the copied state
+    /// (e.g. `"lighten(5%), alpha(0.5)"`).
+    #[serde(default)]
+    pub apply: String,
+}
+
+/// Creates the fallback rules of the states derived from the active state.
+/// The states without color operations are not derived, so they follow the default rules.
+pub(crate) fn derived(states: &[(&str, &Option<String>)]) -> HashMap<String, Fallback> {
+    states
+        .iter()
+        .filter_map(|(state, apply)| {
+            apply.as_ref().map(|apply| {
+                let rule = Fallback {
+                    from: "active".into(),
+                    apply: apply.clone(),
+                };
+
+                (state.to_string(), rule)
+            })
+        })
+        .collect()
+}
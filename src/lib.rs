@@ -2,12 +2,21 @@
 //! It contains a collection of `Style` conertable structures that can be
 //! serialized using `serde`.
 
+// Lets the derive macros refer to this crate as `marcel` inside its own tests.
+extern crate self as marcel;
+
 mod border;
 mod button;
 //pub mod checkbox;
 mod application;
 mod color;
+mod component;
 mod container;
+mod contract;
+mod error;
+mod fallback;
+mod operation;
+mod palette;
 mod panegrid;
 mod picklist;
 mod progressbar;
@@ -15,8 +24,11 @@ mod progressbar;
 //pub mod rule;
 mod scrollable;
 //pub mod slider;
+mod template;
 mod textinput;
+mod token;
 mod tooltip;
+mod warning;
 
 mod theme;
 
@@ -25,31 +37,43 @@ pub use border::Border;
 pub use button::{Button, ButtonState};
 pub use color::Color;
 pub use container::Container;
+pub use contract::{StyleContract, StyleKeys};
+pub use marcel_derive::StyleKeys;
+pub use error::Error;
+pub use fallback::Fallback;
+pub use operation::Operation;
+pub use palette::Palette;
 pub use panegrid::{PaneGrid, PaneGridState};
 pub use picklist::{Picklist, PicklistMenu, PicklistState};
 pub use progressbar::ProgressBar;
 pub use scrollable::{Scrollable, ScrollableState};
+pub use template::{Parameter, ParameterValue, ThemeTemplate};
 pub use textinput::{TextInput, TextInputState};
 pub use tooltip::Tooltip;
+pub use warning::Warning;
 
-pub use theme::Theme;
+pub use theme::{Context, Options, Resolver, StyleId, Theme};
 
 pub mod serial {
 
     pub use crate::{
-        application::serial::Application,
-        border::serial::Border,
-        button::serial::{Button, ButtonComponent, ButtonState},
-        container::serial::Container,
+        application::serial::{Application, ApplicationOverride, Defaults},
+        border::serial::{Border, BorderComponent, BorderValue},
+        button::serial::{Button, ButtonComponent, ButtonState, ButtonStyle},
+        container::serial::{Container, ContainerComponent},
+        fallback::serial::Fallback,
+        palette::serial::Palette,
         panegrid::serial::{PaneGrid, PaneGridComponent, PaneGridState},
         picklist::serial::{
             Picklist, PicklistMenu, PicklistMenuComponent, PicklistState, PicklistStateComponent,
+            PicklistStyle,
         },
-        progressbar::serial::ProgressBar,
-        scrollable::serial::{Scrollable, ScrollableComponent, ScrollableState},
-        textinput::serial::{TextInput, TextInputComponent, TextInputState},
-        tooltip::serial::Tooltip,
+        progressbar::serial::{ProgressBar, ProgressBarComponent},
+        scrollable::serial::{Scrollable, ScrollableAxis, ScrollableComponent, ScrollableState},
+        textinput::serial::{TextInput, TextInputComponent, TextInputState, TextInputStyle},
+        token::serial::Number,
+        tooltip::serial::{Tooltip, TooltipComponent},
     };
 
-    pub use crate::theme::serial::Theme;
+    pub use crate::theme::{serial::Theme, Condition, Conditional, Fragment};
 }
@@ -0,0 +1,307 @@
+//! Color operations, used to derive the colors of a state from another state.
+//!
+//! The lightness and saturation are modified in the OKLCH color space, so the same amount
+//! produces a similar perceived change in all colors.
+
+use crate::{Border, Color, Error};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Operation {
+    /// Increases the perceived lightness by the given amount (0 to 1).
+    Lighten(f32),
+
+    /// Decreases the perceived lightness by the given amount (0 to 1).
+    Darken(f32),
+
+    /// Increases the chroma by the given ratio (0 to 1).
+    Saturate(f32),
+
+    /// Decreases the chroma by the given ratio (0 to 1).
+    Desaturate(f32),
+
+    /// Multiplies the opacity by the given value (0 to 1).
+    Alpha(f32),
+}
+
+impl Operation {
+    /// Attempts to parse an operation, such as `"lighten(8%)"` or `"alpha(0.5)"`.
+    pub fn parse(string: &str) -> Result<Self, Error> {
+        let invalid = || Error::InvalidOperation(string.trim().into());
+
+        let (name, argument) = string
+            .trim()
+            .strip_suffix(')')
+            .ok_or_else(invalid)?
+            .split_once('(')
+            .ok_or_else(invalid)?;
+
+        let argument = argument.trim();
+        let amount = match argument.strip_suffix('%') {
+            Some(percent) => percent.trim().parse::<f32>().map_err(|_| invalid())? / 100.0,
+            _ => argument.parse::<f32>().map_err(|_| invalid())?,
+        };
+
+        match name.trim() {
+            "lighten" => Ok(Operation::Lighten(amount)),
+            "darken" => Ok(Operation::Darken(amount)),
+            "saturate" => Ok(Operation::Saturate(amount)),
+            "desaturate" => Ok(Operation::Desaturate(amount)),
+            "alpha" => Ok(Operation::Alpha(amount)),
+            _ => Err(invalid()),
+        }
+    }
+
+    /// Attempts to parse a comma separated list of operations, such as `"lighten(8%), alpha(0.5)"`.
+    /// An empty string is an empty list.
+    pub fn parse_list(string: &str) -> Result<Vec<Self>, Error> {
+        string
+            .split(',')
+            .filter(|operation| !operation.trim().is_empty())
+            .map(Self::parse)
+            .collect()
+    }
+
+    /// Applies the operation to a color.
+    pub fn apply(&self, color: Color) -> Color {
+        let Color(r, g, b, a) = color;
+
+        match *self {
+            Operation::Alpha(alpha) => Color(r, g, b, (a * alpha).clamp(0.0, 1.0)),
+
+            operation => {
+                let (l, c, h) = oklch(color);
+
+                let (l, c) = match operation {
+                    Operation::Lighten(amount) => (l + amount, c),
+                    Operation::Darken(amount) => (l - amount, c),
+                    Operation::Saturate(amount) => (l, c * (1.0 + amount)),
+                    Operation::Desaturate(amount) => (l, c * (1.0 - amount)),
+                    Operation::Alpha(_) => (l, c),
+                };
+
+                let Color(r, g, b, _) = srgb(l.clamp(0.0, 1.0), c.max(0.0), h);
+                Color(r, g, b, a)
+            }
+        }
+    }
+
+    /// Applies a list of operations, in order, to a color.
+    pub fn apply_all(operations: &[Operation], color: Color) -> Color {
+        operations
+            .iter()
+            .fold(color, |color, operation| operation.apply(color))
+    }
+}
+
+impl core::fmt::Display for Operation {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Operation::Lighten(amount) => write!(f, "lighten({}%)", amount * 100.0),
+            Operation::Darken(amount) => write!(f, "darken({}%)", amount * 100.0),
+            Operation::Saturate(amount) => write!(f, "saturate({}%)", amount * 100.0),
+            Operation::Desaturate(amount) => write!(f, "desaturate({}%)", amount * 100.0),
+            Operation::Alpha(alpha) => write!(f, "alpha({})", alpha),
+        }
+    }
+}
+
+/// Styles whose colors can be transformed by color operations.
+pub(crate) trait Transform: Sized {
+    /// Applies a transformation to all the colors of the style.
+    fn transform(self, f: &dyn Fn(Color) -> Color) -> Self;
+
+    /// Applies a list of operations to all the colors of the style.
+    fn apply(self, operations: &[Operation]) -> Self {
+        self.transform(&|color| Operation::apply_all(operations, color))
+    }
+}
+
+impl Transform for Border {
+    fn transform(self, f: &dyn Fn(Color) -> Color) -> Self {
+        Border {
+            color: f(self.color),
+            ..self
+        }
+    }
+}
+
+/// Converts an sRGB color into OKLCH (lightness, chroma, hue).
+fn oklch(color: Color) -> (f32, f32, f32) {
+    let linear = |c: u8| {
+        let c = c as f32 / 255.0;
+
+        match c <= 0.04045 {
+            true => c / 12.92,
+            _ => ((c + 0.055) / 1.055).powf(2.4),
+        }
+    };
+
+    let (r, g, b) = (linear(color.0), linear(color.1), linear(color.2));
+
+    let l = (0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+    let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+    let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+
+    let lightness = 0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s;
+    let a = 1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s;
+    let b = 0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s;
+
+    (lightness, a.hypot(b), b.atan2(a))
+}
+
+/// Converts an OKLCH (lightness, chroma, hue) color into an opaque sRGB color.
+fn srgb(lightness: f32, chroma: f32, hue: f32) -> Color {
+    let (a, b) = (chroma * hue.cos(), chroma * hue.sin());
+
+    let l = (lightness + 0.396_337_78 * a + 0.215_803_76 * b).powi(3);
+    let m = (lightness - 0.105_561_346 * a - 0.063_854_17 * b).powi(3);
+    let s = (lightness - 0.089_484_18 * a - 1.291_485_5 * b).powi(3);
+
+    let r = 4.076_741_7 * l - 3.307_711_6 * m + 0.230_969_94 * s;
+    let g = -1.268_438 * l + 2.609_757_4 * m - 0.341_319_38 * s;
+    let b = -0.0041960863 * l - 0.703_418_6 * m + 1.707_614_7 * s;
+
+    let gamma = |c: f32| {
+        let c = c.clamp(0.0, 1.0);
+
+        let c = match c <= 0.0031308 {
+            true => c * 12.92,
+            _ => 1.055 * c.powf(1.0 / 2.4) - 0.055,
+        };
+
+        (c * 255.0).round() as u8
+    };
+
+    Color(gamma(r), gamma(g), gamma(b), 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks that two colors differ by at most one unit in each channel.
+    fn close(a: Color, b: Color) -> bool {
+        let channel = |x: u8, y: u8| x.abs_diff(y) <= 1;
+        channel(a.0, b.0) && channel(a.1, b.1) && channel(a.2, b.2) && (a.3 - b.3).abs() < 1e-6
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            Operation::parse("lighten(8%)"),
+            Ok(Operation::Lighten(0.08))
+        );
+        assert_eq!(
+            Operation::parse(" darken( 0.1 ) "),
+            Ok(Operation::Darken(0.1))
+        );
+        assert_eq!(Operation::parse("alpha(0.5)"), Ok(Operation::Alpha(0.5)));
+        assert_eq!(
+            Operation::parse_list("saturate(10%), alpha(50%)"),
+            Ok(vec![Operation::Saturate(0.1), Operation::Alpha(0.5)])
+        );
+        assert_eq!(Operation::parse_list(""), Ok(vec![]));
+    }
+
+    #[test]
+    fn invalid() {
+        for operation in [
+            "lightn(5%)",
+            "lighten(5%",
+            "lighten()",
+            "lighten(a%)",
+            "lighten",
+        ] {
+            assert_eq!(
+                Operation::parse(operation),
+                Err(Error::InvalidOperation(operation.into()))
+            );
+        }
+
+        assert_eq!(
+            Operation::parse_list("lighten(5%), blur(2)"),
+            Err(Error::InvalidOperation("blur(2)".into()))
+        );
+    }
+
+    #[test]
+    fn display() {
+        for operation in [
+            Operation::Lighten(0.25),
+            Operation::Darken(0.5),
+            Operation::Desaturate(0.75),
+            Operation::Alpha(0.5),
+        ] {
+            assert_eq!(Operation::parse(&operation.to_string()), Ok(operation));
+        }
+    }
+
+    #[test]
+    fn oklch_known_colors() {
+        let (l, c, _) = oklch(Color(255, 255, 255, 1.0));
+        assert!((l - 1.0).abs() < 1e-3 && c < 1e-3);
+
+        let (l, c, _) = oklch(Color(0, 0, 0, 1.0));
+        assert!(l.abs() < 1e-3 && c < 1e-3);
+
+        // Reference values of pure red: oklch(62.8% 0.2577 29.23).
+        let (l, c, h) = oklch(Color(255, 0, 0, 1.0));
+        assert!((l - 0.628).abs() < 1e-3);
+        assert!((c - 0.2577).abs() < 1e-3);
+        assert!((h.to_degrees() - 29.23).abs() < 0.1);
+    }
+
+    #[test]
+    fn srgb_round_trip() {
+        for color in [
+            Color(255, 0, 0, 1.0),
+            Color(0, 128, 255, 1.0),
+            Color(30, 30, 30, 1.0),
+            Color(200, 180, 40, 1.0),
+        ] {
+            let (l, c, h) = oklch(color);
+            assert!(close(srgb(l, c, h), color));
+        }
+    }
+
+    #[test]
+    fn lighten_darken_round_trip() {
+        let color = Color(52, 101, 164, 0.8);
+
+        let lighter = Operation::Lighten(0.1).apply(color);
+        let (l, _, _) = oklch(lighter);
+        assert!((l - (oklch(color).0 + 0.1)).abs() < 5e-3);
+        assert_eq!(lighter.3, 0.8);
+
+        let back = Operation::Darken(0.1).apply(lighter);
+        assert!(close(back, color));
+    }
+
+    #[test]
+    fn clamped() {
+        let gray = Color(30, 30, 30, 1.0);
+        assert!(close(
+            Operation::Lighten(2.0).apply(gray),
+            Color(255, 255, 255, 1.0)
+        ));
+        assert!(close(
+            Operation::Darken(2.0).apply(gray),
+            Color(0, 0, 0, 1.0)
+        ));
+
+        // Colors keep their chroma, so the lightest color of a hue is clipped to the sRGB gamut.
+        let (l, _, _) = oklch(Operation::Lighten(2.0).apply(Color(10, 20, 30, 1.0)));
+        assert!(l > 0.97);
+
+        assert_eq!(Operation::Alpha(4.0).apply(Color(1, 2, 3, 0.5)).3, 1.0);
+    }
+
+    #[test]
+    fn apply_all() {
+        let color = Color(52, 101, 164, 1.0);
+        let operations = [Operation::Alpha(0.5), Operation::Alpha(0.5)];
+
+        assert_eq!(Operation::apply_all(&operations, color).3, 0.25);
+        assert_eq!(Operation::apply_all(&[], color), color);
+    }
+}
@@ -0,0 +1,100 @@
+//! Palette theme.
+//! Defines the colors of the built-in `iced` styles.
+//! The extended palette is always generated by `iced` from these colors,
+//! as `iced` 0.7 does not allow custom themes with an extended palette.
+
+pub mod serial;
+
+use crate::{Color, Error, Theme};
+
+#[derive(Clone, Copy, Debug)]
+pub struct Palette {
+    /// Background color.
+    pub background: Color,
+
+    /// Text color.
+    pub text: Color,
+
+    /// Primary color.
+    pub primary: Color,
+
+    /// Success color.
+    pub success: Color,
+
+    /// Danger color.
+    pub danger: Color,
+}
+
+impl Palette {
+    /// Attempts to create a theme from its &serialized version.
+    /// Fails with the first field that references a color that is not defined.
+    pub fn create(serial: &serial::Palette, theme: &Theme) -> Result<Self, Error> {
+        let color = |field, key: &String| {
+            theme.color_key(key).map_err(|_| Error::UnknownKey {
+                section: "palette",
+                field,
+                key: key.clone(),
+            })
+        };
+
+        Ok(Palette {
+            background: color("background", &serial.background)?,
+            text: color("text", &serial.text)?,
+            primary: color("primary", &serial.primary)?,
+            success: color("success", &serial.success)?,
+            danger: color("danger", &serial.danger)?,
+        })
+    }
+}
+
+impl From<Palette> for iced::theme::Palette {
+    fn from(palette: Palette) -> Self {
+        iced::theme::Palette {
+            background: palette.background.into(),
+            text: palette.text.into(),
+            primary: palette.primary.into(),
+            success: palette.success.into(),
+            danger: palette.danger.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn palette(primary: &str) -> serial::Palette {
+        serial::Palette {
+            background: "white".into(),
+            text: "black".into(),
+            primary: primary.into(),
+            success: "#00ff00".into(),
+            danger: "red".into(),
+        }
+    }
+
+    #[test]
+    fn colors() {
+        let mut theme = Theme::new();
+        theme.color.insert("white".into(), Color(255, 255, 255, 1.0));
+
+        let palette = Palette::create(&palette("#0000ff"), &theme).unwrap();
+
+        assert_eq!(palette.background, Color(255, 255, 255, 1.0));
+        assert_eq!(palette.primary, Color(0, 0, 255, 1.0));
+    }
+
+    #[test]
+    fn unknown_keys() {
+        let error = Palette::create(&palette("accent"), &Theme::new()).unwrap_err();
+
+        assert_eq!(
+            error,
+            Error::UnknownKey {
+                section: "palette",
+                field: "primary",
+                key: "accent".into(),
+            }
+        );
+    }
+}
@@ -0,0 +1,21 @@
+//! `serde` compatible version of the palette theme.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Palette {
+    /// Key to the background color.
+    pub background: String,
+
+    /// Key to the text color.
+    pub text: String,
+
+    /// Key to the primary color.
+    pub primary: String,
+
+    /// Key to the success color.
+    pub success: String,
+
+    /// Key to the danger color.
+    pub danger: String,
+}
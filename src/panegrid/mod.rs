@@ -2,7 +2,12 @@
 
 pub mod serial;
 
-use crate::{Color, Theme};
+use crate::{
+    fallback,
+    operation::Transform,
+    theme::cross::{self, split, state, Shared},
+    Color, Theme,
+};
 
 use iced::widget::pane_grid::{Line, StyleSheet};
 
@@ -21,38 +26,68 @@ impl PaneGrid {
         let picked = Self::state(&serial.picked, theme, 0)?;
         let hovered = Self::state(&serial.hovered, theme, 1)?;
 
-        // Find the first state theme that is not None.
-        let default = match (picked, hovered) {
-            (Some(d), _) => d,
-            (_, Some(d)) => d,
+        // Fill the states that are not defined.
+        let rules = fallback::create(&serial.fallback, "panegrid").map_err(|_| ())?;
+        let state = fallback::resolve("panegrid", [picked, hovered], &rules, theme)?;
 
-            _ => return Err(()),
+        Ok(PaneGrid { state })
+    }
+
+    /// Creates a state from the shared fields of a style of another category.
+    /// Pane grids do not share any field, so all of them must be overridden.
+    fn cross(serial: &PaneGridComponent, _: Shared, theme: &Theme) -> Result<PaneGridState, ()> {
+        let (color, width) = match serial {
+            PaneGridComponent::Extends {
+                color,
+                width,
+                ..
+            } => (color, width),
+            _ => (&None, &None),
         };
 
-        Ok(PaneGrid {
-            state: [
-                if picked.is_some() {
-                    picked.unwrap()
-                } else {
-                    default
-                },
-                if hovered.is_some() {
-                    hovered.unwrap()
-                } else {
-                    default
-                },
-            ],
+        Ok(PaneGridState {
+            color: cross::color(theme, color, None)?,
+            width: theme.number(width.as_ref().ok_or(())?)?,
         })
     }
 
     fn state(serial: &serial::PaneGridComponent, theme: &Theme, index: usize) -> Result<Option<PaneGridState>, ()> {
+        // Inherit the shared fields of a style of another category.
+        if let Some((reference, _)) = serial.parent() {
+            if let (Some(category), name) = split(reference, "panegrid") {
+                let shared = theme.shared(category, name, state("panegrid", index))?;
+                return Self::cross(serial, shared, theme).map(Some);
+            }
+        }
+
         match &serial {
             PaneGridComponent::Defined(state) => Ok(Some(PaneGridState::from(&state, &theme)?)),
 
-            PaneGridComponent::Inherited(name) => match theme.panegrid.get(name.as_str()) {
-                Some(panegrid) => Ok(Some(panegrid.state[index].clone())),
-                _ => Err(()),
-            },
+            PaneGridComponent::Inherited(name) => {
+                match theme.inherited("panegrid", &theme.panegrid, name) {
+                    Some(panegrid) => Ok(Some(panegrid.state[index].clone())),
+                    _ => Err(()),
+                }
+            }
+
+            PaneGridComponent::Extends { from, color, width } => {
+                match theme.inherited("panegrid", &theme.panegrid, from) {
+                    Some(panegrid) => {
+                        let mut state = panegrid.state[index];
+
+                        if let Some(key) = color {
+                            state.color = theme.color_key(key)?;
+                        }
+
+                        if let Some(width) = width {
+                            state.width = theme.number(width)?;
+                        }
+
+                        Ok(Some(state))
+                    }
+                    _ => Err(()),
+                }
+            }
 
             PaneGridComponent::None => Ok(None),
         }
@@ -63,17 +98,11 @@ impl StyleSheet for PaneGrid {
     type Style = iced::Theme;
 
     fn picked_split(&self, _: &Self::Style) -> Option<Line> {
-        Some(Line {
-            color: self.state[0].color.into(),
-            width: self.state[0].width.into(),
-        })
+        Some(self.state[0].into())
     }
 
     fn hovered_split(&self, _: &Self::Style) -> Option<Line> {
-        Some(Line {
-            color: self.state[1].color.into(),
-            width: self.state[1].width.into(),
-        })
+        Some(self.state[1].into())
     }
 }
 
@@ -86,18 +115,33 @@ pub struct PaneGridState {
     pub width: f32,
 }
 
+impl From<PaneGridState> for Line {
+    fn from(state: PaneGridState) -> Self {
+        Line {
+            color: state.color.into(),
+            width: state.width,
+        }
+    }
+}
+
+impl Transform for PaneGridState {
+    fn transform(self, f: &dyn Fn(Color) -> Color) -> Self {
+        PaneGridState {
+            color: f(self.color),
+            ..self
+        }
+    }
+}
+
 impl PaneGridState {
     /// Attempts to create a theme from its &serialized version.
     fn from(serial: &serial::PaneGridState, theme: &Theme) -> Result<Self, ()> {
         // Get the background color.
-        let color = match theme.color.get(serial.color.as_str()) {
-            Some(color) => *color,
-            _ => return Err(()),
-        };
+        let color = theme.color_key(&serial.color)?;
 
         Ok(PaneGridState {
             color,
-            width: serial.width,
+            width: theme.number(&serial.width)?,
         })
     }
 }
@@ -1,14 +1,28 @@
 //! `serde` compatible version of the pane grid theme.
 
+use crate::{
+    component::compact,
+    serial::{Fallback, Number},
+    theme::cross::overrides,
+};
+
 use serde::{Deserialize, Serialize};
 
+use std::collections::HashMap;
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct PaneGrid {
     /// Picked state.
+    #[serde(default)]
     pub picked: PaneGridComponent,
 
     /// Hovered state.
+    #[serde(default)]
     pub hovered: PaneGridComponent,
+
+    /// Fallback rules of the states that are not defined, mapped by the name of the state.
+    #[serde(default)]
+    pub fallback: HashMap<String, Fallback>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -17,12 +31,26 @@ pub struct PaneGridState {
     pub color: String,
 
     /// Width of the line.
-    pub width: f32,
+    pub width: Number,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-#[serde(tag = "type")]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(remote = "Self", tag = "type")]
 pub enum PaneGridComponent {
+    /// The line state is inherited from another pane grid theme, overriding some of its fields.
+    Extends {
+        /// Key to the parent pane grid theme.
+        from: String,
+
+        /// The color of the line.
+        #[serde(default)]
+        color: Option<String>,
+
+        /// Width of the line.
+        #[serde(default)]
+        width: Option<Number>,
+    },
+
     /// The line state is defined.
     Defined(PaneGridState),
 
@@ -30,5 +58,31 @@ pub enum PaneGridComponent {
     Inherited(String),
 
     /// The line state is not defined.
+    #[default]
     None,
 }
+
+compact!(PaneGridComponent);
+
+impl PaneGridComponent {
+    /// Returns the reference to the parent style and its overridden fields, if it is inherited.
+    pub(crate) fn parent(&self) -> Option<(&str, Vec<&'static str>)> {
+        match self {
+            PaneGridComponent::Inherited(name) => Some((name, Vec::new())),
+
+            PaneGridComponent::Extends {
+                from,
+                color,
+                width,
+            } => Some((
+                from,
+                overrides(&[
+                    ("color", color.is_some()),
+                    ("width", width.is_some()),
+                ]),
+            )),
+
+            _ => None,
+        }
+    }
+}
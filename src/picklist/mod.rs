@@ -2,9 +2,19 @@
 
 pub mod serial;
 
-use crate::{Border, Color, Theme};
+use crate::{
+    fallback,
+    operation::Transform,
+    theme::cross::{self, split, state, Shared},
+    Border, Color, Theme,
+};
 
-use iced::widget::pick_list::{Appearance, StyleSheet};
+use iced::{
+    overlay::menu,
+    widget::pick_list::{Appearance, StyleSheet},
+};
+
+use std::rc::Rc;
 
 use serial::{PicklistMenuComponent, PicklistStateComponent};
 
@@ -19,7 +29,10 @@ pub struct Picklist {
 
 impl Picklist {
     /// Attempts to create a theme from its &serialized version.
-    pub fn create(serial: &serial::Picklist, theme: &Theme) -> Result<Self, ()> {
+    pub fn create(serial: &serial::PicklistStyle, theme: &Theme) -> Result<Self, ()> {
+        // Expand the derived states.
+        let serial = serial.states();
+
         // Get all the themes.
         let active = Self::state(&serial.active, theme, 0)?;
         let hovered = Self::state(&serial.hovered, theme, 1)?;
@@ -27,29 +40,37 @@ impl Picklist {
         // Get the menu style.
         let menu = Self::menu(&serial.menu, theme)?;
 
-        // Find the first state theme that is not None.
-        let default = match (active, hovered) {
-            (Some(d), _) => d,
-            (_, Some(d)) => d,
+        // Fill the states that are not defined.
+        let rules = fallback::create(&serial.fallback, "picklist").map_err(|_| ())?;
+        let state = fallback::resolve("picklist", [active, hovered], &rules, theme)?;
 
-            _ => return Err(()),
-        };
+        Ok(Picklist { state, menu })
+    }
 
-        Ok(Picklist {
-            state: [
-                if active.is_some() {
-                    active.unwrap()
-                } else {
-                    default
-                },
-                if hovered.is_some() {
-                    hovered.unwrap()
-                } else {
-                    default
-                },
-            ],
+    /// Creates a state from the shared fields of a style of another category.
+    fn cross(
+        serial: &PicklistStateComponent,
+        shared: Shared,
+        theme: &Theme,
+    ) -> Result<PicklistState, ()> {
+        let (background, text, placeholder, border, handle) = match serial {
+            PicklistStateComponent::Extends {
+                background,
+                text,
+                placeholder,
+                border,
+                handle,
+                ..
+            } => (background, text, placeholder, border, handle),
+            _ => (&None, &None, &None, &None, &None),
+        };
 
-            menu,
+        Ok(PicklistState {
+            background: cross::color(theme, background, shared.background)?,
+            text: cross::color(theme, text, shared.text)?,
+            placeholder: cross::color(theme, placeholder, None)?,
+            border: cross::border(theme, border, shared.border)?,
+            handle: cross::color(theme, handle, None)?,
         })
     }
 
@@ -58,11 +79,57 @@ impl Picklist {
         theme: &Theme,
         index: usize,
     ) -> Result<Option<PicklistState>, ()> {
+        // Inherit the shared fields of a style of another category.
+        if let Some((reference, _)) = serial.parent() {
+            if let (Some(category), name) = split(reference, "picklist") {
+                let shared = theme.shared(category, name, state("picklist", index))?;
+                return Self::cross(serial, shared, theme).map(Some);
+            }
+        }
+
         match &serial {
             PicklistStateComponent::Defined(state) => Ok(Some(PicklistState::from(&state, &theme)?)),
 
-            PicklistStateComponent::Inherited(name) => match theme.picklist.get(name.as_str()) {
-                Some(picklist) => Ok(Some(picklist.state[index].clone())),
+            PicklistStateComponent::Inherited(name) => {
+                match theme.inherited("picklist", &theme.picklist, name) {
+                    Some(picklist) => Ok(Some(picklist.state[index].clone())),
+                    _ => Err(()),
+                }
+            }
+
+            PicklistStateComponent::Extends {
+                from,
+                background,
+                text,
+                placeholder,
+                border,
+                handle,
+            } => match theme.inherited("picklist", &theme.picklist, from) {
+                Some(picklist) => {
+                    let mut state = picklist.state[index];
+
+                    if let Some(key) = background {
+                        state.background = theme.color_key(key)?;
+                    }
+
+                    if let Some(key) = text {
+                        state.text = theme.color_key(key)?;
+                    }
+
+                    if let Some(key) = placeholder {
+                        state.placeholder = theme.color_key(key)?;
+                    }
+
+                    if let Some(key) = border {
+                        state.border = theme.border_value(key)?;
+                    }
+
+                    if let Some(key) = handle {
+                        state.handle = theme.color_key(key)?;
+                    }
+
+                    Ok(Some(state))
+                }
                 _ => Err(()),
             },
 
@@ -70,43 +137,110 @@ impl Picklist {
         }
     }
 
+    /// Creates a menu from the shared fields of a style of another category.
+    fn cross_menu(
+        serial: &PicklistMenuComponent,
+        shared: Shared,
+        theme: &Theme,
+    ) -> Result<PicklistMenu, ()> {
+        let (background, text, border, sbackground, stext) = match serial {
+            PicklistMenuComponent::Extends {
+                background,
+                text,
+                border,
+                sbackground,
+                stext,
+                ..
+            } => (background, text, border, sbackground, stext),
+            _ => (&None, &None, &None, &None, &None),
+        };
+
+        Ok(PicklistMenu {
+            background: [
+                cross::color(theme, background, shared.background)?,
+                cross::color(theme, sbackground, None)?,
+            ],
+            text: [
+                cross::color(theme, text, shared.text)?,
+                cross::color(theme, stext, None)?,
+            ],
+            border: cross::border(theme, border, shared.border)?,
+        })
+    }
+
     fn menu(serial: &serial::PicklistMenuComponent, theme: &Theme) -> Result<PicklistMenu, ()> {
+        // Inherit the shared fields of a style of another category.
+        if let Some((reference, _)) = serial.parent() {
+            if let (Some(category), name) = split(reference, "picklist") {
+                let shared = theme.shared(category, name, state("picklist", 0))?;
+                return Self::cross_menu(serial, shared, theme);
+            }
+        }
+
         match &serial {
             PicklistMenuComponent::Defined(state) => Ok(PicklistMenu::from(&state, &theme)?),
 
-            PicklistMenuComponent::Inherited(name) => match theme.picklist.get(name.as_str()) {
-                Some(picklist) => Ok(picklist.menu.clone()),
+            PicklistMenuComponent::Inherited(name) => {
+                match theme.inherited("picklist", &theme.picklist, name) {
+                    Some(picklist) => Ok(picklist.menu.clone()),
+                    _ => Err(()),
+                }
+            }
+
+            PicklistMenuComponent::Extends {
+                from,
+                background,
+                text,
+                border,
+                sbackground,
+                stext,
+            } => match theme.inherited("picklist", &theme.picklist, from) {
+                Some(picklist) => {
+                    let mut menu = picklist.menu;
+
+                    if let Some(key) = background {
+                        menu.background[0] = theme.color_key(key)?;
+                    }
+
+                    if let Some(key) = text {
+                        menu.text[0] = theme.color_key(key)?;
+                    }
+
+                    if let Some(key) = border {
+                        menu.border = theme.border_value(key)?;
+                    }
+
+                    if let Some(key) = sbackground {
+                        menu.background[1] = theme.color_key(key)?;
+                    }
+
+                    if let Some(key) = stext {
+                        menu.text[1] = theme.color_key(key)?;
+                    }
+
+                    Ok(menu)
+                }
                 _ => Err(()),
             },
         }
     }
 }
 
+impl From<Picklist> for iced::theme::PickList {
+    fn from(picklist: Picklist) -> Self {
+        iced::theme::PickList::Custom(Rc::new(picklist), Rc::new(picklist.menu))
+    }
+}
+
 impl StyleSheet for Picklist {
     type Style = iced::Theme;
 
     fn active(&self, _: &Self::Style) -> Appearance {
-        Appearance {
-            text_color: self.state[0].text.into(),
-            placeholder_color: self.state[0].placeholder.into(),
-            background: self.state[0].background.into(),
-            border_radius: self.state[0].border.radius,
-            border_width: self.state[0].border.width,
-            border_color: self.state[0].border.color.into(),
-            handle_color: self.state[0].handle.into(),
-        }
+        self.state[0].into()
     }
 
     fn hovered(&self, _: &Self::Style) -> Appearance {
-        Appearance {
-            text_color: self.state[1].text.into(),
-            placeholder_color: self.state[1].placeholder.into(),
-            background: self.state[1].background.into(),
-            border_radius: self.state[1].border.radius,
-            border_width: self.state[1].border.width,
-            border_color: self.state[1].border.color.into(),
-            handle_color: self.state[1].handle.into(),
-        }
+        self.state[1].into()
     }
 }
 
@@ -128,37 +262,48 @@ pub struct PicklistState {
     pub handle: Color,
 }
 
+impl From<PicklistState> for Appearance {
+    fn from(state: PicklistState) -> Self {
+        Appearance {
+            text_color: state.text.into(),
+            placeholder_color: state.placeholder.into(),
+            background: state.background.into(),
+            border_radius: state.border.radius,
+            border_width: state.border.width,
+            border_color: state.border.color.into(),
+            handle_color: state.handle.into(),
+        }
+    }
+}
+
+impl Transform for PicklistState {
+    fn transform(self, f: &dyn Fn(Color) -> Color) -> Self {
+        PicklistState {
+            background: f(self.background),
+            text: f(self.text),
+            placeholder: f(self.placeholder),
+            border: self.border.transform(f),
+            handle: f(self.handle),
+        }
+    }
+}
+
 impl PicklistState {
     /// Attempts to create a theme from its &serialized version.
     fn from(serial: &serial::PicklistState, theme: &Theme) -> Result<Self, ()> {
         // Get the background color.
-        let background = match theme.color.get(serial.background.as_str()) {
-            Some(color) => *color,
-            _ => return Err(()),
-        };
+        let background = theme.color_key(&serial.background)?;
 
         // Get the text color.
-        let text = match theme.color.get(serial.text.as_str()) {
-            Some(color) => *color,
-            _ => return Err(()),
-        };
+        let text = theme.color_key(&serial.text)?;
 
         // Get the placeholder color.
-        let placeholder = match theme.color.get(serial.placeholder.as_str()) {
-            Some(color) => *color,
-            _ => return Err(()),
-        };
+        let placeholder = theme.color_key(&serial.placeholder)?;
 
         // Get the background color.
-        let border = match theme.border.get(serial.border.as_str()) {
-            Some(border) => *border,
-            _ => return Err(()),
-        };
+        let border = theme.border_value(&serial.border)?;
 
-        let handle = match theme.color.get(serial.handle.as_str()) {
-            Some(color) => *color,
-            _ => return Err(()),
-        };
+        let handle = theme.color_key(&serial.handle)?;
 
         Ok(PicklistState {
             background,
@@ -182,36 +327,43 @@ pub struct PicklistMenu {
     pub border: Border,
 }
 
+impl menu::StyleSheet for PicklistMenu {
+    type Style = iced::Theme;
+
+    fn appearance(&self, _: &Self::Style) -> menu::Appearance {
+        (*self).into()
+    }
+}
+
+impl From<PicklistMenu> for menu::Appearance {
+    fn from(menu: PicklistMenu) -> Self {
+        menu::Appearance {
+            text_color: menu.text[0].into(),
+            background: menu.background[0].into(),
+            border_width: menu.border.width,
+            border_radius: menu.border.radius,
+            border_color: menu.border.color.into(),
+            selected_text_color: menu.text[1].into(),
+            selected_background: menu.background[1].into(),
+        }
+    }
+}
+
 impl PicklistMenu {
     /// Attempts to create a theme from its &serialized version.
     fn from(serial: &serial::PicklistMenu, theme: &Theme) -> Result<Self, ()> {
         // Get the background colors.
-        let background = match theme.color.get(serial.background.as_str()) {
-            Some(color) => *color,
-            _ => return Err(()),
-        };
+        let background = theme.color_key(&serial.background)?;
 
-        let sbackground = match theme.color.get(serial.sbackground.as_str()) {
-            Some(color) => *color,
-            _ => return Err(()),
-        };
+        let sbackground = theme.color_key(&serial.sbackground)?;
 
         // Get the text color.
-        let text = match theme.color.get(serial.text.as_str()) {
-            Some(color) => *color,
-            _ => return Err(()),
-        };
+        let text = theme.color_key(&serial.text)?;
 
-        let stext = match theme.color.get(serial.stext.as_str()) {
-            Some(color) => *color,
-            _ => return Err(()),
-        };
+        let stext = theme.color_key(&serial.stext)?;
 
         // Get the background color.
-        let border = match theme.border.get(serial.border.as_str()) {
-            Some(border) => *border,
-            _ => return Err(()),
-        };
+        let border = theme.border_value(&serial.border)?;
 
         Ok(PicklistMenu {
             background: [background, sbackground],
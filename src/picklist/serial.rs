@@ -1,17 +1,75 @@
 //! `serde` compatible version of the pick list theme.
 
+use crate::{
+    component::{compact, derivable},
+    fallback::serial::derived,
+    serial::{BorderValue, Fallback},
+    theme::cross::overrides,
+};
+
 use serde::{Deserialize, Serialize};
 
+use std::{borrow::Cow, collections::HashMap};
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Picklist {
     /// Active state.
+    #[serde(default)]
     pub active: PicklistStateComponent,
 
     /// Hovered state.
+    #[serde(default)]
     pub hovered: PicklistStateComponent,
 
     /// Menu theme.
     pub menu: PicklistMenuComponent,
+
+    /// Fallback rules of the states that are not defined, mapped by the name of the state.
+    #[serde(default)]
+    pub fallback: HashMap<String, Fallback>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(remote = "Self", tag = "type")]
+pub enum PicklistStyle {
+    /// All the states are derived from a base state, applying color operations to it.
+    Derived {
+        /// Base (active) state.
+        base: PicklistStateComponent,
+
+        /// Color operations that derive the hovered state (e.g. `"lighten(8%)"`).
+        #[serde(default)]
+        hovered: Option<String>,
+
+        /// Menu theme.
+        menu: PicklistMenuComponent,
+    },
+
+    /// All the states are given.
+    States(Picklist),
+}
+
+derivable!(PicklistStyle);
+
+impl PicklistStyle {
+    /// Returns the states of the picklist.
+    /// The derived states are not defined, and are created by their fallback rules.
+    pub(crate) fn states(&self) -> Cow<'_, Picklist> {
+        match self {
+            PicklistStyle::Derived {
+                base,
+                hovered,
+                menu,
+            } => Cow::Owned(Picklist {
+                active: base.clone(),
+                hovered: PicklistStateComponent::None,
+                menu: menu.clone(),
+                fallback: derived(&[("hovered", hovered)]),
+            }),
+
+            PicklistStyle::States(picklist) => Cow::Borrowed(picklist),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -25,8 +83,8 @@ pub struct PicklistState {
     /// Key to the placeholder color.
     pub placeholder: String,
 
-    /// Key to the border theme.
-    pub border: String,
+    /// Key to the border theme, or inline border.
+    pub border: BorderValue,
 
     /// Handle color.
     pub handle: String,
@@ -40,8 +98,8 @@ pub struct PicklistMenu {
     /// Key to the text color.
     pub text: String,
 
-    /// Key to the border theme.
-    pub border: String,
+    /// Key to the border theme, or inline border.
+    pub border: BorderValue,
 
     /// Key to the selected background color.
     pub sbackground: String,
@@ -50,9 +108,35 @@ pub struct PicklistMenu {
     pub stext: String,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-#[serde(tag = "type")]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(remote = "Self", tag = "type")]
 pub enum PicklistStateComponent {
+    /// The picklist state is inherited from another picklist theme, overriding some of its fields.
+    Extends {
+        /// Key to the parent picklist theme.
+        from: String,
+
+        /// Key to the background color.
+        #[serde(default)]
+        background: Option<String>,
+
+        /// Key to the text color.
+        #[serde(default)]
+        text: Option<String>,
+
+        /// Key to the placeholder color.
+        #[serde(default)]
+        placeholder: Option<String>,
+
+        /// Key to the border theme, or inline border.
+        #[serde(default)]
+        border: Option<BorderValue>,
+
+        /// Key to the handle color.
+        #[serde(default)]
+        handle: Option<String>,
+    },
+
     /// The button state is defined.
     Defined(PicklistState),
 
@@ -60,15 +144,104 @@ pub enum PicklistStateComponent {
     Inherited(String),
 
     /// The button state is not defined.
+    #[default]
     None,
 }
 
+compact!(PicklistStateComponent);
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
-#[serde(tag = "type")]
+#[serde(remote = "Self", tag = "type")]
 pub enum PicklistMenuComponent {
+    /// The menu is inherited from another picklist theme, overriding some of its fields.
+    Extends {
+        /// Key to the parent picklist theme.
+        from: String,
+
+        /// Key to the background color.
+        #[serde(default)]
+        background: Option<String>,
+
+        /// Key to the text color.
+        #[serde(default)]
+        text: Option<String>,
+
+        /// Key to the border theme, or inline border.
+        #[serde(default)]
+        border: Option<BorderValue>,
+
+        /// Key to the selected background color.
+        #[serde(default)]
+        sbackground: Option<String>,
+
+        /// Key to the selected text color.
+        #[serde(default)]
+        stext: Option<String>,
+    },
+
     /// The button state is defined.
     Defined(PicklistMenu),
 
     /// The button state is inherited from another button theme.
     Inherited(String),
 }
+
+compact!(PicklistMenuComponent);
+
+impl PicklistStateComponent {
+    /// Returns the reference to the parent style and its overridden fields, if it is inherited.
+    pub(crate) fn parent(&self) -> Option<(&str, Vec<&'static str>)> {
+        match self {
+            PicklistStateComponent::Inherited(name) => Some((name, Vec::new())),
+
+            PicklistStateComponent::Extends {
+                from,
+                background,
+                text,
+                placeholder,
+                border,
+                handle,
+            } => Some((
+                from,
+                overrides(&[
+                    ("background", background.is_some()),
+                    ("text", text.is_some()),
+                    ("placeholder", placeholder.is_some()),
+                    ("border", border.is_some()),
+                    ("handle", handle.is_some()),
+                ]),
+            )),
+
+            _ => None,
+        }
+    }
+}
+
+impl PicklistMenuComponent {
+    /// Returns the reference to the parent style and its overridden fields, if it is inherited.
+    pub(crate) fn parent(&self) -> Option<(&str, Vec<&'static str>)> {
+        match self {
+            PicklistMenuComponent::Inherited(name) => Some((name, Vec::new())),
+
+            PicklistMenuComponent::Extends {
+                from,
+                background,
+                text,
+                border,
+                sbackground,
+                stext,
+            } => Some((
+                from,
+                overrides(&[
+                    ("background", background.is_some()),
+                    ("text", text.is_some()),
+                    ("border", border.is_some()),
+                    ("sbackground", sbackground.is_some()),
+                    ("stext", stext.is_some()),
+                ]),
+            )),
+
+            _ => None,
+        }
+    }
+}
@@ -2,10 +2,15 @@
 
 pub mod serial;
 
-use crate::{Color, Theme};
+use crate::{
+    theme::cross::{self, split, state, Shared},
+    Color, Theme,
+};
 
 use iced::widget::progress_bar::{Appearance, StyleSheet};
 
+use serial::ProgressBarComponent;
+
 #[derive(Clone, Copy, Debug)]
 pub struct ProgressBar {
     /// Background color.
@@ -20,23 +25,88 @@ pub struct ProgressBar {
 
 impl ProgressBar {
     /// Attempts to create a theme from its &serialized version.
-    pub fn create(serial: &serial::ProgressBar, theme: &Theme) -> Result<Self, ()> {
-        // Get the color of the progress bar background.
-        let background = match theme.color.get(serial.background.as_str()) {
-            Some(color) => *color,
-            _ => return Err(()),
+    pub fn create(serial: &ProgressBarComponent, theme: &Theme) -> Result<Self, ()> {
+        // Inherit the shared fields of a style of another category.
+        if let Some((reference, _)) = serial.parent() {
+            if let (Some(category), name) = split(reference, "progressbar") {
+                let shared = theme.shared(category, name, state("progressbar", 0))?;
+                return Self::cross(serial, shared, theme);
+            }
+        }
+
+        match serial {
+            ProgressBarComponent::Defined(progressbar) => Self::from(progressbar, theme),
+
+            ProgressBarComponent::Inherited(name) => {
+                match theme.inherited("progressbar", &theme.progressbar, name) {
+                    Some(progressbar) => Ok(*progressbar),
+                    _ => Err(()),
+                }
+            }
+
+            ProgressBarComponent::Extends {
+                from,
+                background,
+                bar,
+                radius,
+            } => match theme.inherited("progressbar", &theme.progressbar, from) {
+                Some(progressbar) => {
+                    let mut progressbar = *progressbar;
+
+                    if let Some(key) = background {
+                        progressbar.background = theme.color_key(key)?;
+                    }
+
+                    if let Some(key) = bar {
+                        progressbar.bar = theme.color_key(key)?;
+                    }
+
+                    if let Some(radius) = radius {
+                        progressbar.radius = theme.number(radius)?;
+                    }
+
+                    Ok(progressbar)
+                }
+                _ => Err(()),
+            },
+        }
+    }
+
+    /// Creates a style from the shared fields of a style of another category.
+    fn cross(
+        serial: &ProgressBarComponent,
+        shared: Shared,
+        theme: &Theme,
+    ) -> Result<ProgressBar, ()> {
+        let (background, bar, radius) = match serial {
+            ProgressBarComponent::Extends {
+                background,
+                bar,
+                radius,
+                ..
+            } => (background, bar, radius),
+            _ => (&None, &None, &None),
         };
 
+        Ok(ProgressBar {
+            background: cross::color(theme, background, shared.background)?,
+            bar: cross::color(theme, bar, None)?,
+            radius: theme.number(radius.as_ref().ok_or(())?)?,
+        })
+    }
+
+    /// Attempts to create a theme from its &serialized definition.
+    fn from(serial: &serial::ProgressBar, theme: &Theme) -> Result<Self, ()> {
+        // Get the color of the progress bar background.
+        let background = theme.color_key(&serial.background)?;
+
         // Get the border of the progress bar bar.
-        let bar = match theme.color.get(serial.bar.as_str()) {
-            Some(color) => *color,
-            _ => return Err(()),
-        };
+        let bar = theme.color_key(&serial.bar)?;
 
         Ok(ProgressBar {
             background,
             bar,
-            radius: serial.radius,
+            radius: theme.number(&serial.radius)?,
         })
     }
 }
@@ -45,10 +115,16 @@ impl StyleSheet for ProgressBar {
     type Style = iced::Theme;
 
     fn appearance(&self, _: &Self::Style) -> Appearance {
+        (*self).into()
+    }
+}
+
+impl From<ProgressBar> for Appearance {
+    fn from(progressbar: ProgressBar) -> Self {
         Appearance {
-            background: self.background.into(),
-            bar: self.bar.into(),
-            border_radius: self.radius,
+            background: progressbar.background.into(),
+            bar: progressbar.bar.into(),
+            border_radius: progressbar.radius,
         }
     }
 }
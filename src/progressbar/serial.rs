@@ -1,5 +1,7 @@
 //! `serde` compatible version of the container theme.
 
+use crate::{component::compact, serial::Number, theme::cross::overrides};
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -11,5 +13,61 @@ pub struct ProgressBar {
     pub bar: String,
 
     /// Border radius.
-    pub radius: f32,
+    pub radius: Number,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(remote = "Self", tag = "type")]
+pub enum ProgressBarComponent {
+    /// The progress bar is inherited from another progress bar theme,
+    /// overriding some of its fields.
+    Extends {
+        /// Key to the parent progress bar theme.
+        from: String,
+
+        /// Key to the background color.
+        #[serde(default)]
+        background: Option<String>,
+
+        /// Key to the bar color.
+        #[serde(default)]
+        bar: Option<String>,
+
+        /// Border radius.
+        #[serde(default)]
+        radius: Option<Number>,
+    },
+
+    /// The progress bar is defined.
+    Defined(ProgressBar),
+
+    /// The progress bar is inherited from another progress bar theme.
+    Inherited(String),
+}
+
+compact!(ProgressBarComponent);
+
+impl ProgressBarComponent {
+    /// Returns the reference to the parent style and its overridden fields, if it is inherited.
+    pub(crate) fn parent(&self) -> Option<(&str, Vec<&'static str>)> {
+        match self {
+            ProgressBarComponent::Inherited(name) => Some((name, Vec::new())),
+
+            ProgressBarComponent::Extends {
+                from,
+                background,
+                bar,
+                radius,
+            } => Some((
+                from,
+                overrides(&[
+                    ("background", background.is_some()),
+                    ("bar", bar.is_some()),
+                    ("radius", radius.is_some()),
+                ]),
+            )),
+
+            ProgressBarComponent::Defined(_) => None,
+        }
+    }
 }
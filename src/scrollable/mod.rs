@@ -2,7 +2,12 @@
 
 pub mod serial;
 
-use crate::{Border, Color, Theme};
+use crate::{
+    fallback::{self, Fallback},
+    operation::Transform,
+    theme::cross::{self, split, state, Shared},
+    Border, Color, Theme,
+};
 
 use iced_native::widget::scrollable::{
     style::{Scrollbar, Scroller},
@@ -11,57 +16,143 @@ use iced_native::widget::scrollable::{
 
 use serial::ScrollableComponent;
 
+use std::collections::HashMap;
+
 #[derive(Clone, Copy, Debug)]
 pub struct Scrollable {
     /// State Themes of the scrollable.
     /// In order: active, hovered, dragging.
     pub state: [ScrollableState; 3],
+
+    /// State Themes of the horizontal scrollbar.
+    /// In order: active, hovered, dragging.
+    pub horizontal: [ScrollableState; 3],
 }
 
 impl Scrollable {
     /// Attempts to create a theme from its &serialized version.
     pub fn create(serial: &serial::Scrollable, theme: &Theme) -> Result<Self, ()> {
+        // Get the fallback rules of both scrollbars.
+        let rules = fallback::create(&serial.fallback, "scrollable").map_err(|_| ())?;
+
+        // Get the vertical scrollbar states.
+        let state = Self::states(
+            [&serial.active, &serial.hovered, &serial.dragging],
+            &rules,
+            theme,
+            false,
+        )?;
+
+        // Get the horizontal scrollbar states, defaulting to the vertical ones.
+        let horizontal = match &serial.horizontal {
+            Some(axis) => Self::states(
+                [&axis.active, &axis.hovered, &axis.dragging],
+                &rules,
+                theme,
+                true,
+            )?,
+            _ => state,
+        };
+
+        Ok(Scrollable { state, horizontal })
+    }
+
+    fn states(
+        serial: [&ScrollableComponent; 3],
+        rules: &HashMap<String, Fallback>,
+        theme: &Theme,
+        horizontal: bool,
+    ) -> Result<[ScrollableState; 3], ()> {
         // Get all the themes.
-        let active = Self::state(&serial.active, theme, 0)?;
-        let hovered = Self::state(&serial.hovered, theme, 1)?;
-        let dragging = Self::state(&serial.dragging, theme, 2)?;
+        let active = Self::state(serial[0], theme, 0, horizontal)?;
+        let hovered = Self::state(serial[1], theme, 1, horizontal)?;
+        let dragging = Self::state(serial[2], theme, 2, horizontal)?;
 
-        // Find the first state theme that is not None.
-        let default = match (active, hovered, dragging) {
-            (Some(d), _, _) => d,
-            (_, Some(d), _) => d,
-            (_, _, Some(d)) => d,
+        // Fill the states that are not defined.
+        fallback::resolve("scrollable", [active, hovered, dragging], rules, theme)
+    }
 
-            _ => return Err(()),
+    /// Creates a state from the shared fields of a style of another category.
+    fn cross(
+        serial: &ScrollableComponent,
+        shared: Shared,
+        theme: &Theme,
+    ) -> Result<ScrollableState, ()> {
+        let (color, border, scolor, sborder) = match serial {
+            ScrollableComponent::Extends {
+                color,
+                border,
+                scolor,
+                sborder,
+                ..
+            } => (color, border, scolor, sborder),
+            _ => (&None, &None, &None, &None),
         };
 
-        Ok(Scrollable {
-            state: [
-                if active.is_some() {
-                    active.unwrap()
-                } else {
-                    default
-                },
-                if hovered.is_some() {
-                    hovered.unwrap()
-                } else {
-                    default
-                },
-                if dragging.is_some() {
-                    dragging.unwrap()
-                } else {
-                    default
-                },
-            ],
+        Ok(ScrollableState {
+            color: cross::color(theme, color, shared.background)?,
+            border: cross::border(theme, border, shared.border)?,
+            scolor: cross::color(theme, scolor, None)?,
+            sborder: cross::border(theme, sborder, None)?,
         })
     }
 
-    fn state(serial: &ScrollableComponent, theme: &Theme, index: usize) -> Result<Option<ScrollableState>, ()> {
+    fn state(
+        serial: &ScrollableComponent,
+        theme: &Theme,
+        index: usize,
+        horizontal: bool,
+    ) -> Result<Option<ScrollableState>, ()> {
+        // Inherit the shared fields of a style of another category.
+        if let Some((reference, _)) = serial.parent() {
+            if let (Some(category), name) = split(reference, "scrollable") {
+                let shared = theme.shared(category, name, state("scrollable", index))?;
+                return Self::cross(serial, shared, theme).map(Some);
+            }
+        }
+
         match &serial {
-            ScrollableComponent::Defined(state) => Ok(Some(ScrollableState::from(&state, &theme)?)),
+            ScrollableComponent::Defined(state) => Ok(Some(ScrollableState::from(state, theme)?)),
+
+            ScrollableComponent::Inherited(name) => {
+                match theme.inherited("scrollable", &theme.scrollable, name) {
+                    Some(scrollable) if horizontal => Ok(Some(scrollable.horizontal[index])),
+                    Some(scrollable) => Ok(Some(scrollable.state[index])),
+                    _ => Err(()),
+                }
+            }
+
+            ScrollableComponent::Extends {
+                from,
+                color,
+                border,
+                scolor,
+                sborder,
+            } => match theme.inherited("scrollable", &theme.scrollable, from) {
+                Some(scrollable) => {
+                    let mut state = match horizontal {
+                        true => scrollable.horizontal[index],
+                        _ => scrollable.state[index],
+                    };
+
+                    if let Some(key) = color {
+                        state.color = theme.color_key(key)?;
+                    }
+
+                    if let Some(key) = border {
+                        state.border = theme.border_value(key)?;
+                    }
+
+                    if let Some(key) = scolor {
+                        state.scolor = theme.color_key(key)?;
+                    }
+
+                    if let Some(key) = sborder {
+                        state.sborder = theme.border_value(key)?;
+                    }
 
-            ScrollableComponent::Inherited(name) => match theme.scrollable.get(name.as_str()) {
-                Some(scrollable) => Ok(Some(scrollable.state[index].clone())),
+                    Ok(Some(state))
+                }
                 _ => Err(()),
             },
 
@@ -74,35 +165,27 @@ impl StyleSheet for Scrollable {
     type Style = iced::Theme;
 
     fn active(&self, _: &Self::Style) -> Scrollbar {
-        Scrollbar {
-            background: Some(self.state[0].color.into()),
-            border_radius: self.state[0].border.radius,
-            border_width: self.state[0].border.width,
-            border_color: self.state[0].border.color.into(),
-
-            scroller: Scroller {
-                color: self.state[0].scolor.into(),
-                border_radius: self.state[0].sborder.radius,
-                border_width: self.state[0].sborder.width,
-                border_color: self.state[0].sborder.color.into(),
-            },
-        }
+        self.state[0].into()
     }
 
     fn hovered(&self, _: &Self::Style) -> Scrollbar {
-        Scrollbar {
-            background: Some(self.state[1].color.into()),
-            border_radius: self.state[1].border.radius,
-            border_width: self.state[1].border.width,
-            border_color: self.state[1].border.color.into(),
+        self.state[1].into()
+    }
 
-            scroller: Scroller {
-                color: self.state[1].scolor.into(),
-                border_radius: self.state[1].sborder.radius,
-                border_width: self.state[1].sborder.width,
-                border_color: self.state[1].sborder.color.into(),
-            },
-        }
+    fn dragging(&self, _: &Self::Style) -> Scrollbar {
+        self.state[2].into()
+    }
+
+    fn active_horizontal(&self, _: &Self::Style) -> Scrollbar {
+        self.horizontal[0].into()
+    }
+
+    fn hovered_horizontal(&self, _: &Self::Style) -> Scrollbar {
+        self.horizontal[1].into()
+    }
+
+    fn dragging_horizontal(&self, _: &Self::Style) -> Scrollbar {
+        self.horizontal[2].into()
     }
 }
 
@@ -121,32 +204,49 @@ pub struct ScrollableState {
     pub sborder: Border,
 }
 
+impl From<ScrollableState> for Scrollbar {
+    fn from(state: ScrollableState) -> Self {
+        Scrollbar {
+            background: Some(state.color.into()),
+            border_radius: state.border.radius,
+            border_width: state.border.width,
+            border_color: state.border.color.into(),
+
+            scroller: Scroller {
+                color: state.scolor.into(),
+                border_radius: state.sborder.radius,
+                border_width: state.sborder.width,
+                border_color: state.sborder.color.into(),
+            },
+        }
+    }
+}
+
+impl Transform for ScrollableState {
+    fn transform(self, f: &dyn Fn(Color) -> Color) -> Self {
+        ScrollableState {
+            color: f(self.color),
+            border: self.border.transform(f),
+            scolor: f(self.scolor),
+            sborder: self.sborder.transform(f),
+        }
+    }
+}
+
 impl ScrollableState {
     /// Attempts to create a theme from its &serialized version.
     fn from(serial: &serial::ScrollableState, theme: &Theme) -> Result<Self, ()> {
         // Get the scrollable color.
-        let color = match theme.color.get(serial.color.as_str()) {
-            Some(color) => *color,
-            _ => return Err(()),
-        };
+        let color = theme.color_key(&serial.color)?;
 
         // Get the scrollable border.
-        let border = match theme.border.get(serial.border.as_str()) {
-            Some(border) => *border,
-            _ => return Err(()),
-        };
+        let border = theme.border_value(&serial.border)?;
 
         // Get the scroller color.
-        let scolor = match theme.color.get(serial.scolor.as_str()) {
-            Some(color) => *color,
-            _ => return Err(()),
-        };
+        let scolor = theme.color_key(&serial.scolor)?;
 
         // Get the scroller border.
-        let sborder = match theme.border.get(serial.sborder.as_str()) {
-            Some(border) => *border,
-            _ => return Err(()),
-        };
+        let sborder = theme.border_value(&serial.sborder)?;
 
         Ok(ScrollableState {
             color,
@@ -1,16 +1,52 @@
 //! `serde` compatible version of the scrollbar theme.
 
+use crate::{
+    component::compact,
+    serial::{BorderValue, Fallback},
+    theme::cross::overrides,
+};
+
 use serde::{Deserialize, Serialize};
 
+use std::collections::HashMap;
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Scrollable {
     /// Active state.
+    #[serde(default)]
+    pub active: ScrollableComponent,
+
+    /// Hovered state.
+    #[serde(default)]
+    pub hovered: ScrollableComponent,
+
+    /// Dragging state.
+    #[serde(default)]
+    pub dragging: ScrollableComponent,
+
+    /// States of the horizontal scrollbar.
+    /// If not present, the horizontal scrollbar uses the vertical states.
+    #[serde(default)]
+    pub horizontal: Option<ScrollableAxis>,
+
+    /// Fallback rules of the states that are not defined, mapped by the name of the state.
+    /// The rules apply to both scrollbars.
+    #[serde(default)]
+    pub fallback: HashMap<String, Fallback>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ScrollableAxis {
+    /// Active state.
+    #[serde(default)]
     pub active: ScrollableComponent,
 
     /// Hovered state.
+    #[serde(default)]
     pub hovered: ScrollableComponent,
 
     /// Dragging state.
+    #[serde(default)]
     pub dragging: ScrollableComponent,
 }
 
@@ -19,19 +55,41 @@ pub struct ScrollableState {
     /// Key to the background color.
     pub color: String,
 
-    /// Key to the border theme.
-    pub border: String,
+    /// Key to the border theme, or inline border.
+    pub border: BorderValue,
 
     /// Key to the scroller color.
     pub scolor: String,
 
-    /// Key to the scroller border.
-    pub sborder: String,
+    /// Key to the scroller border, or inline border.
+    pub sborder: BorderValue,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-#[serde(tag = "type")]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(remote = "Self", tag = "type")]
 pub enum ScrollableComponent {
+    /// The scrollable state is inherited from another scrollable theme, overriding some of its fields.
+    Extends {
+        /// Key to the parent scrollable theme.
+        from: String,
+
+        /// Key to the background color.
+        #[serde(default)]
+        color: Option<String>,
+
+        /// Key to the border theme, or inline border.
+        #[serde(default)]
+        border: Option<BorderValue>,
+
+        /// Key to the scroller color.
+        #[serde(default)]
+        scolor: Option<String>,
+
+        /// Key to the scroller border, or inline border.
+        #[serde(default)]
+        sborder: Option<BorderValue>,
+    },
+
     /// The button state is defined.
     Defined(ScrollableState),
 
@@ -39,5 +97,35 @@ pub enum ScrollableComponent {
     Inherited(String),
 
     /// The button state is not defined.
+    #[default]
     None,
 }
+
+compact!(ScrollableComponent);
+
+impl ScrollableComponent {
+    /// Returns the reference to the parent style and its overridden fields, if it is inherited.
+    pub(crate) fn parent(&self) -> Option<(&str, Vec<&'static str>)> {
+        match self {
+            ScrollableComponent::Inherited(name) => Some((name, Vec::new())),
+
+            ScrollableComponent::Extends {
+                from,
+                color,
+                border,
+                scolor,
+                sborder,
+            } => Some((
+                from,
+                overrides(&[
+                    ("color", color.is_some()),
+                    ("border", border.is_some()),
+                    ("scolor", scolor.is_some()),
+                    ("sborder", sborder.is_some()),
+                ]),
+            )),
+
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,246 @@
+//! Parameterized themes.
+//!
+//! A template is a serialized theme with declared parameters. Instantiating a template adds the
+//! color parameters to the colors of the theme and the numeric parameters to its tokens, so they
+//! can be referenced by name in any color or numeric field. Boolean parameters are added to the
+//! tokens as `1` or `0`, so they can be used in numeric expressions.
+
+use crate::{
+    serial::{Number, Theme},
+    Color, Error,
+};
+
+use serde::{Deserialize, Serialize};
+
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ThemeTemplate {
+    /// Maps name keys to the declared parameters.
+    pub parameters: HashMap<String, Parameter>,
+
+    /// Theme that references the parameters.
+    pub theme: Theme,
+}
+
+/// Declaration of a template parameter.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "type")]
+pub enum Parameter {
+    /// Color parameter.
+    Color {
+        /// Value used when the parameter is not given.
+        #[serde(default)]
+        default: Option<Color>,
+    },
+
+    /// Numeric parameter.
+    Number {
+        /// Value used when the parameter is not given.
+        #[serde(default)]
+        default: Option<f32>,
+    },
+
+    /// Boolean parameter.
+    Bool {
+        /// Value used when the parameter is not given.
+        #[serde(default)]
+        default: Option<bool>,
+    },
+}
+
+/// Value given to a template parameter.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum ParameterValue {
+    /// Boolean value.
+    Bool(bool),
+
+    /// Numeric value.
+    Number(f32),
+
+    /// Color value.
+    Color(Color),
+}
+
+impl Parameter {
+    /// Returns the default value of the parameter, if any.
+    fn default(&self) -> Option<ParameterValue> {
+        match *self {
+            Parameter::Color { default } => default.map(ParameterValue::Color),
+            Parameter::Number { default } => default.map(ParameterValue::Number),
+            Parameter::Bool { default } => default.map(ParameterValue::Bool),
+        }
+    }
+
+    /// Checks if a value has the type of the parameter.
+    fn accepts(&self, value: &ParameterValue) -> bool {
+        matches!(
+            (self, value),
+            (Parameter::Color { .. }, ParameterValue::Color(_))
+                | (Parameter::Number { .. }, ParameterValue::Number(_))
+                | (Parameter::Bool { .. }, ParameterValue::Bool(_))
+        )
+    }
+}
+
+impl ThemeTemplate {
+    /// Creates the theme with the given parameters.
+    /// The parameters that are not given take their default value.
+    pub fn instantiate(&self, params: &HashMap<String, ParameterValue>) -> Result<Theme, Error> {
+        if let Some(name) = params.keys().find(|name| !self.parameters.contains_key(*name)) {
+            return Err(Error::UnknownParameter(name.clone()));
+        }
+
+        let mut theme = self.theme.clone();
+
+        for (name, parameter) in &self.parameters {
+            let value = match params.get(name).copied().or_else(|| parameter.default()) {
+                Some(value) => value,
+                _ => return Err(Error::MissingParameter(name.clone())),
+            };
+
+            if !parameter.accepts(&value) {
+                return Err(Error::MismatchedParameter(name.clone()));
+            }
+
+            match value {
+                ParameterValue::Color(color) => {
+                    theme.color.insert(name.clone(), color);
+                }
+
+                ParameterValue::Number(number) => {
+                    theme.tokens.insert(name.clone(), Number::Value(number));
+                }
+
+                ParameterValue::Bool(flag) => {
+                    let number = if flag { 1.0 } else { 0.0 };
+                    theme.tokens.insert(name.clone(), Number::Value(number));
+                }
+            }
+        }
+
+        Ok(theme)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Theme as NormalTheme;
+
+    const WHITE: Color = Color(255, 255, 255, 1.0);
+    const BLUE: Color = Color(0x33, 0x66, 0xff, 1.0);
+
+    fn template() -> ThemeTemplate {
+        ron::from_str(
+            r##"(
+                parameters: {
+                    "accent": (type: "Color", default: Some("#3366ff")),
+                    "base": (type: "Color"),
+                    "round": (type: "Number", default: Some(4.0)),
+                    "rounded": (type: "Bool", default: Some(true)),
+                },
+                theme: (
+                    name: "test",
+                    description: "",
+                    application: (background_color: "base", text_color: "black"),
+                    color: { "black": "#000000" },
+                    border: { "thin": (color: "accent", radius: "round * rounded", width: 1.0) },
+                ),
+            )"##,
+        )
+        .unwrap()
+    }
+
+    fn params(params: &[(&str, ParameterValue)]) -> HashMap<String, ParameterValue> {
+        params
+            .iter()
+            .map(|(name, value)| (name.to_string(), *value))
+            .collect()
+    }
+
+    #[test]
+    fn defaults() {
+        let theme = template()
+            .instantiate(&params(&[("base", ParameterValue::Color(WHITE))]))
+            .unwrap();
+
+        assert_eq!(theme.color["accent"], BLUE);
+        assert_eq!(theme.color["base"], WHITE);
+        assert_eq!(theme.tokens["round"], Number::Value(4.0));
+
+        let theme = NormalTheme::parse(&theme).unwrap();
+        assert_eq!(theme.border["thin"].color, BLUE);
+        assert_eq!(theme.border["thin"].radius, 4.0);
+    }
+
+    #[test]
+    fn given_values() {
+        let theme = template()
+            .instantiate(&params(&[
+                ("base", ParameterValue::Color(WHITE)),
+                ("accent", ParameterValue::Color(WHITE)),
+                ("round", ParameterValue::Number(6.0)),
+            ]))
+            .unwrap();
+
+        assert_eq!(theme.color["accent"], WHITE);
+        assert_eq!(theme.tokens["round"], Number::Value(6.0));
+    }
+
+    #[test]
+    fn bool_tokens() {
+        let template = template();
+        let base = ("base", ParameterValue::Color(WHITE));
+
+        let theme = template.instantiate(&params(&[base])).unwrap();
+        assert_eq!(theme.tokens["rounded"], Number::Value(1.0));
+
+        let off = ("rounded", ParameterValue::Bool(false));
+        let theme = template.instantiate(&params(&[base, off])).unwrap();
+        assert_eq!(theme.tokens["rounded"], Number::Value(0.0));
+
+        let theme = NormalTheme::parse(&theme).unwrap();
+        assert_eq!(theme.border["thin"].radius, 0.0);
+    }
+
+    #[test]
+    fn unknown_parameters() {
+        let error = template()
+            .instantiate(&params(&[
+                ("base", ParameterValue::Color(WHITE)),
+                ("size", ParameterValue::Number(1.0)),
+            ]))
+            .unwrap_err();
+
+        assert_eq!(error, Error::UnknownParameter("size".into()));
+    }
+
+    #[test]
+    fn missing_parameters() {
+        let error = template().instantiate(&HashMap::new()).unwrap_err();
+
+        assert_eq!(error, Error::MissingParameter("base".into()));
+    }
+
+    #[test]
+    fn mismatched_parameters() {
+        let base = ("base", ParameterValue::Color(WHITE));
+
+        for (name, value) in [
+            ("base", ParameterValue::Number(1.0)),
+            ("round", ParameterValue::Bool(true)),
+            ("rounded", ParameterValue::Number(1.0)),
+        ] {
+            let given = if name == "base" {
+                params(&[(name, value)])
+            } else {
+                params(&[base, (name, value)])
+            };
+
+            let error = template().instantiate(&given).unwrap_err();
+            assert_eq!(error, Error::MismatchedParameter(name.into()));
+        }
+    }
+}
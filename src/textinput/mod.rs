@@ -2,7 +2,12 @@
 
 pub mod serial;
 
-use crate::{Border, Color, Theme};
+use crate::{
+    fallback,
+    operation::Transform,
+    theme::cross::{self, split, state, Shared},
+    Border, Color, Theme,
+};
 
 use iced::widget::text_input::{Appearance, StyleSheet};
 
@@ -20,68 +25,92 @@ pub struct TextInput {
 
 impl TextInput {
     /// Attempts to create a theme from its &serialized version.
-    pub fn create(serial: &serial::TextInput, theme: &Theme) -> Result<Self, ()> {
+    pub fn create(serial: &serial::TextInputStyle, theme: &Theme) -> Result<Self, ()> {
+        // Expand the derived states.
+        let serial = serial.states();
+
         // Get all the themes.
         let active = Self::state(&serial.active, theme, 0)?;
         let hovered = Self::state(&serial.hovered, theme, 1)?;
         let focused = Self::state(&serial.focused, theme, 2)?;
 
-        // Find the first state theme that is not None.
-        let default = match (active, hovered, focused) {
-            (Some(d), _, _) => d,
-            (_, Some(d), _) => d,
-            (_, _, Some(d)) => d,
-
-            _ => return Err(()),
-        };
+        // Fill the states that are not defined.
+        let rules = fallback::create(&serial.fallback, "textinput").map_err(|_| ())?;
+        let state = fallback::resolve("textinput", [active, hovered, focused], &rules, theme)?;
 
         // Get the placeholder color.
-        let placeholder = match theme.color.get(serial.placeholder.as_str()) {
-            Some(color) => *color,
-            _ => return Err(()),
-        };
+        let placeholder = theme.color_key(&serial.placeholder)?;
 
         // Get the placeholder color.
-        let value = match theme.color.get(serial.value.as_str()) {
-            Some(color) => *color,
-            _ => return Err(()),
-        };
+        let value = theme.color_key(&serial.value)?;
 
         // Get the placeholder color.
-        let selection = match theme.color.get(serial.selection.as_str()) {
-            Some(color) => *color,
-            _ => return Err(()),
-        };
+        let selection = theme.color_key(&serial.selection)?;
 
         Ok(TextInput {
-            state: [
-                if active.is_some() {
-                    active.unwrap()
-                } else {
-                    default
-                },
-                if hovered.is_some() {
-                    hovered.unwrap()
-                } else {
-                    default
-                },
-                if focused.is_some() {
-                    focused.unwrap()
-                } else {
-                    default
-                },
-            ],
-
+            state,
             colors: [placeholder, value, selection],
         })
     }
 
+    /// Creates a state from the shared fields of a style of another category.
+    fn cross(
+        serial: &TextInputComponent,
+        shared: Shared,
+        theme: &Theme,
+    ) -> Result<TextInputState, ()> {
+        let (background, border) = match serial {
+            TextInputComponent::Extends {
+                background,
+                border,
+                ..
+            } => (background, border),
+            _ => (&None, &None),
+        };
+
+        Ok(TextInputState {
+            background: cross::color(theme, background, shared.background)?,
+            border: cross::border(theme, border, shared.border)?,
+        })
+    }
+
     fn state(serial: &TextInputComponent, theme: &Theme, index: usize) -> Result<Option<TextInputState>, ()> {
+        // Inherit the shared fields of a style of another category.
+        if let Some((reference, _)) = serial.parent() {
+            if let (Some(category), name) = split(reference, "textinput") {
+                let shared = theme.shared(category, name, state("textinput", index))?;
+                return Self::cross(serial, shared, theme).map(Some);
+            }
+        }
+
         match serial {
             TextInputComponent::Defined(state) => Ok(Some(TextInputState::from(state, &theme)?)),
 
-            TextInputComponent::Inherited(name) => match theme.textinput.get(name.as_str()) {
-                Some(textinput) => Ok(Some(textinput.state[index].clone())),
+            TextInputComponent::Inherited(name) => {
+                match theme.inherited("textinput", &theme.textinput, name) {
+                    Some(textinput) => Ok(Some(textinput.state[index].clone())),
+                    _ => Err(()),
+                }
+            }
+
+            TextInputComponent::Extends {
+                from,
+                background,
+                border,
+            } => match theme.inherited("textinput", &theme.textinput, from) {
+                Some(textinput) => {
+                    let mut state = textinput.state[index];
+
+                    if let Some(key) = background {
+                        state.background = theme.color_key(key)?;
+                    }
+
+                    if let Some(key) = border {
+                        state.border = theme.border_value(key)?;
+                    }
+
+                    Ok(Some(state))
+                }
                 _ => Err(()),
             },
 
@@ -94,30 +123,15 @@ impl StyleSheet for TextInput {
     type Style = iced::Theme;
 
     fn active(&self, _: &Self::Style) -> Appearance {
-        Appearance {
-            background: self.state[0].background.into(),
-            border_radius: self.state[0].border.radius,
-            border_width: self.state[0].border.width,
-            border_color: self.state[0].border.color.into(),
-        }
+        self.state[0].into()
     }
 
     fn hovered(&self, _: &Self::Style) -> Appearance {
-        Appearance {
-            background: self.state[1].background.into(),
-            border_radius: self.state[1].border.radius,
-            border_width: self.state[1].border.width,
-            border_color: self.state[1].border.color.into(),
-        }
+        self.state[1].into()
     }
 
     fn focused(&self, _: &Self::Style) -> Appearance {
-        Appearance {
-            background: self.state[2].background.into(),
-            border_radius: self.state[2].border.radius,
-            border_width: self.state[2].border.width,
-            border_color: self.state[2].border.color.into(),
-        }
+        self.state[2].into()
     }
 
     fn placeholder_color(&self, _: &Self::Style) -> iced::Color {
@@ -142,20 +156,34 @@ pub struct TextInputState {
     pub border: Border,
 }
 
+impl From<TextInputState> for Appearance {
+    fn from(state: TextInputState) -> Self {
+        Appearance {
+            background: state.background.into(),
+            border_radius: state.border.radius,
+            border_width: state.border.width,
+            border_color: state.border.color.into(),
+        }
+    }
+}
+
+impl Transform for TextInputState {
+    fn transform(self, f: &dyn Fn(Color) -> Color) -> Self {
+        TextInputState {
+            background: f(self.background),
+            border: self.border.transform(f),
+        }
+    }
+}
+
 impl TextInputState {
     /// Attempts to create a theme from its &serialized version.
     fn from(serial: &serial::TextInputState, theme: &Theme) -> Result<Self, ()> {
         // Get the background color.
-        let background = match theme.color.get(serial.background.as_str()) {
-            Some(color) => *color,
-            _ => return Err(()),
-        };
+        let background = theme.color_key(&serial.background)?;
 
         // Get the background color.
-        let border = match theme.border.get(serial.border.as_str()) {
-            Some(border) => *border,
-            _ => return Err(()),
-        };
+        let border = theme.border_value(&serial.border)?;
 
         Ok(TextInputState { background, border })
     }
@@ -1,16 +1,28 @@
 //! `serde` compatible version of the text input theme.
 
+use crate::{
+    component::{compact, derivable},
+    fallback::serial::derived,
+    serial::{BorderValue, Fallback},
+    theme::cross::overrides,
+};
+
 use serde::{Deserialize, Serialize};
 
+use std::{borrow::Cow, collections::HashMap};
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct TextInput {
     /// Active state.
+    #[serde(default)]
     pub active: TextInputComponent,
 
     /// Hovered state.
+    #[serde(default)]
     pub hovered: TextInputComponent,
 
     /// Focused state.
+    #[serde(default)]
     pub focused: TextInputComponent,
 
     /// Placeholder color.
@@ -21,6 +33,69 @@ pub struct TextInput {
 
     /// Selection color.
     pub selection: String,
+
+    /// Fallback rules of the states that are not defined, mapped by the name of the state.
+    #[serde(default)]
+    pub fallback: HashMap<String, Fallback>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(remote = "Self", tag = "type")]
+pub enum TextInputStyle {
+    /// All the states are derived from a base state, applying color operations to it.
+    Derived {
+        /// Base (active) state.
+        base: TextInputComponent,
+
+        /// Color operations that derive the hovered state (e.g. `"lighten(8%)"`).
+        #[serde(default)]
+        hovered: Option<String>,
+
+        /// Color operations that derive the focused state (e.g. `"lighten(8%)"`).
+        #[serde(default)]
+        focused: Option<String>,
+
+        /// Placeholder color.
+        placeholder: String,
+
+        /// Value color.
+        value: String,
+
+        /// Selection color.
+        selection: String,
+    },
+
+    /// All the states are given.
+    States(TextInput),
+}
+
+derivable!(TextInputStyle);
+
+impl TextInputStyle {
+    /// Returns the states of the text input.
+    /// The derived states are not defined, and are created by their fallback rules.
+    pub(crate) fn states(&self) -> Cow<'_, TextInput> {
+        match self {
+            TextInputStyle::Derived {
+                base,
+                hovered,
+                focused,
+                placeholder,
+                value,
+                selection,
+            } => Cow::Owned(TextInput {
+                active: base.clone(),
+                hovered: TextInputComponent::None,
+                focused: TextInputComponent::None,
+                placeholder: placeholder.clone(),
+                value: value.clone(),
+                selection: selection.clone(),
+                fallback: derived(&[("hovered", hovered), ("focused", focused)]),
+            }),
+
+            TextInputStyle::States(textinput) => Cow::Borrowed(textinput),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -28,13 +103,27 @@ pub struct TextInputState {
     /// Key to the background color.
     pub background: String,
 
-    /// Key to the border theme.
-    pub border: String,
+    /// Key to the border theme, or inline border.
+    pub border: BorderValue,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-#[serde(tag = "type")]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(remote = "Self", tag = "type")]
 pub enum TextInputComponent {
+    /// The text input state is inherited from another text input theme, overriding some of its fields.
+    Extends {
+        /// Key to the parent text input theme.
+        from: String,
+
+        /// Key to the background color.
+        #[serde(default)]
+        background: Option<String>,
+
+        /// Key to the border theme, or inline border.
+        #[serde(default)]
+        border: Option<BorderValue>,
+    },
+
     /// The button state is defined.
     Defined(TextInputState),
 
@@ -42,5 +131,31 @@ pub enum TextInputComponent {
     Inherited(String),
 
     /// The button state is not defined.
+    #[default]
     None,
 }
+
+compact!(TextInputComponent);
+
+impl TextInputComponent {
+    /// Returns the reference to the parent style and its overridden fields, if it is inherited.
+    pub(crate) fn parent(&self) -> Option<(&str, Vec<&'static str>)> {
+        match self {
+            TextInputComponent::Inherited(name) => Some((name, Vec::new())),
+
+            TextInputComponent::Extends {
+                from,
+                background,
+                border,
+            } => Some((
+                from,
+                overrides(&[
+                    ("background", background.is_some()),
+                    ("border", border.is_some()),
+                ]),
+            )),
+
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,287 @@
+//! Aliases of renamed keys.
+//!
+//! An alias maps the old key of an entry to its new key. The entries that a theme defines with
+//! the old key are also available with the new key, and each of them is reported as a
+//! deprecation warning, so theme authors can rename them gradually. The aliases of a
+//! &serialized theme are applied before any entry is created, so the styles can reference the
+//! new keys.
+
+use super::{serial, Theme};
+use crate::Warning;
+
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+};
+
+/// Copies the entries of a category defined with an old key to their new key.
+/// Returns the old keys defined in the category. An alias to a key that is already defined is
+/// reported as shadowed and ignored.
+fn alias<T: Clone>(
+    category: &'static str,
+    entries: &mut HashMap<String, T>,
+    aliases: &HashMap<String, String>,
+    warnings: &mut Vec<Warning>,
+) -> HashSet<String> {
+    let mut used = HashSet::new();
+
+    for (old, new) in aliases {
+        if entries.contains_key(old) && entries.contains_key(new) {
+            used.insert(old.clone());
+
+            warnings.push(Warning::Shadowed {
+                category,
+                name: old.clone(),
+                replacement: new.clone(),
+            });
+        }
+    }
+
+    let mut aliased = Vec::new();
+
+    // Each pass follows one more alias of chained aliases (e.g. `"a"` to `"b"` to `"c"`).
+    // Only the entries defined with an old key are reported.
+    loop {
+        let mut changed = false;
+
+        for (old, new) in aliases {
+            if entries.contains_key(new) {
+                continue;
+            }
+
+            if let Some(entry) = entries.get(old).cloned() {
+                entries.insert(new.clone(), entry);
+                used.insert(old.clone());
+                changed = true;
+                aliased.push(new);
+
+                if !aliased.contains(&old) {
+                    warnings.push(Warning::Deprecated {
+                        category,
+                        name: old.clone(),
+                        replacement: new.clone(),
+                    });
+                }
+            }
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    used
+}
+
+impl serial::Theme {
+    /// Applies the aliases of this theme to all its categories.
+    /// The aliases whose old key is not defined in any category are reported.
+    pub(crate) fn aliased(&self, warnings: &mut Vec<Warning>) -> Cow<'_, Self> {
+        if self.aliases.is_empty() {
+            return Cow::Borrowed(self);
+        }
+
+        let mut theme = self.clone();
+        let (a, w) = (&self.aliases, &mut *warnings);
+
+        let used: HashSet<String> = [
+            alias("border", &mut theme.border, a, w),
+            alias("button", &mut theme.button, a, w),
+            alias("color", &mut theme.color, a, w),
+            alias("container", &mut theme.container, a, w),
+            alias("panegrid", &mut theme.panegrid, a, w),
+            alias("picklist", &mut theme.picklist, a, w),
+            alias("progressbar", &mut theme.progressbar, a, w),
+            alias("scrollable", &mut theme.scrollable, a, w),
+            alias("text", &mut theme.text, a, w),
+            alias("textinput", &mut theme.textinput, a, w),
+            alias("token", &mut theme.tokens, a, w),
+            alias("tooltip", &mut theme.tooltip, a, w),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        for (old, new) in &self.aliases {
+            if !used.contains(old) {
+                warnings.push(Warning::UnknownAlias {
+                    name: old.clone(),
+                    replacement: new.clone(),
+                });
+            }
+        }
+
+        warnings.sort();
+
+        Cow::Owned(theme)
+    }
+}
+
+impl Theme {
+    /// Applies a table of aliases, from old keys to new keys, to every category of this theme
+    /// and of its scopes. Applications can use it for the keys they renamed, in addition to the
+    /// aliases of the &serialized theme. The entries are copied after they are created, so the
+    /// styles of the theme can only reference the new keys through the &serialized aliases.
+    pub fn alias(&mut self, aliases: &HashMap<String, String>) {
+        self.aliases
+            .extend(aliases.iter().map(|(old, new)| (old.clone(), new.clone())));
+
+        self.apply_aliases(aliases);
+    }
+
+    /// Applies a table of aliases to this theme and its scopes, without recording it.
+    fn apply_aliases(&mut self, aliases: &HashMap<String, String>) {
+        self.renamed.extend(aliases.keys().cloned());
+
+        let w = &mut self.warnings;
+
+        alias("border", &mut self.border, aliases, w);
+        alias("button", &mut self.button, aliases, w);
+        alias("color", &mut self.color, aliases, w);
+        alias("container", &mut self.container, aliases, w);
+        alias("panegrid", &mut self.panegrid, aliases, w);
+        alias("picklist", &mut self.picklist, aliases, w);
+        alias("progressbar", &mut self.progressbar, aliases, w);
+        alias("scrollable", &mut self.scrollable, aliases, w);
+        alias("text", &mut self.text, aliases, w);
+        alias("textinput", &mut self.textinput, aliases, w);
+        alias("token", &mut self.tokens, aliases, w);
+        alias("tooltip", &mut self.tooltip, aliases, w);
+
+        w.sort();
+
+        for scope in self.scopes.values_mut() {
+            scope.apply_aliases(aliases);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+
+    const WHITE: Color = Color(255, 255, 255, 1.0);
+    const BLACK: Color = Color(0, 0, 0, 1.0);
+
+    fn parse(entries: &str) -> Theme {
+        let theme: serial::Theme = ron::from_str(&format!(
+            r##"(
+                name: "test",
+                description: "",
+                application: (background_color: "white", text_color: "black"),
+                color: {{ "white": "#ffffff", "black": "#000000" }},
+                border: {{ "thin": (color: "black", radius: 1.0, width: 1.0) }},
+                {}
+            )"##,
+            entries
+        ))
+        .unwrap();
+
+        Theme::parse(&theme).unwrap()
+    }
+
+    fn deprecated(category: &'static str, name: &str, replacement: &str) -> Warning {
+        Warning::Deprecated {
+            category,
+            name: name.into(),
+            replacement: replacement.into(),
+        }
+    }
+
+    #[test]
+    fn inherited_aliases() {
+        let theme = parse(
+            r#"
+            aliases: { "primary": "button.primary" },
+            button: {
+                "primary": (active: (background: "white", text: "black", border: "thin")),
+                "secondary": (active: (from: "button.primary", text: Some("white"))),
+            },
+            "#,
+        );
+
+        assert_eq!(theme.button["button.primary"].state[0].background, WHITE);
+        assert_eq!(theme.button["secondary"].state[0].text, WHITE);
+        assert_eq!(
+            theme.warnings,
+            vec![deprecated("button", "primary", "button.primary")]
+        );
+    }
+
+    #[test]
+    fn colors_and_tokens() {
+        let theme = parse(
+            r#"
+            aliases: { "black": "text.primary", "radius": "radius.md" },
+            tokens: { "radius": 4.0 },
+            container: { "card": (color: "text.primary", border: "thin") },
+            progressbar: { "bar": (background: "white", bar: "black", radius: "radius.md") },
+            "#,
+        );
+
+        assert_eq!(theme.color["text.primary"], BLACK);
+        assert_eq!(theme.container["card"].color, BLACK);
+        assert_eq!(theme.tokens["radius.md"], 4.0);
+        assert_eq!(theme.progressbar["bar"].radius, 4.0);
+        assert_eq!(
+            theme.warnings,
+            vec![
+                deprecated("color", "black", "text.primary"),
+                deprecated("token", "radius", "radius.md"),
+            ]
+        );
+    }
+
+    #[test]
+    fn shadowed_aliases() {
+        let theme = parse(
+            r#"
+            aliases: { "old": "new" },
+            button: {
+                "old": (active: (background: "white", text: "black", border: "thin")),
+                "new": (active: (background: "black", text: "white", border: "thin")),
+            },
+            "#,
+        );
+
+        assert_eq!(theme.button["new"].state[0].background, BLACK);
+        assert_eq!(
+            theme.warnings,
+            vec![Warning::Shadowed {
+                category: "button",
+                name: "old".into(),
+                replacement: "new".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn unknown_aliases() {
+        let theme = parse(r#"aliases: { "missing": "new" }"#);
+
+        assert_eq!(
+            theme.warnings,
+            vec![Warning::UnknownAlias {
+                name: "missing".into(),
+                replacement: "new".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn application_aliases() {
+        let mut theme = parse(
+            r##"
+            container: { "card": (color: "white", border: "thin") },
+            scopes: { "dark": (color: { "white": "#000000" }) },
+            "##,
+        );
+
+        theme.alias(&HashMap::from([("card".into(), "panel".into())]));
+
+        assert_eq!(theme.container["panel"].color, WHITE);
+        assert_eq!(theme.scoped("dark").container["panel"].color, BLACK);
+        assert_eq!(theme.warnings, vec![deprecated("container", "card", "panel")]);
+    }
+}
@@ -0,0 +1,273 @@
+//! Cascading of hierarchical style keys (e.g. `"primary.small"` to `"primary"`).
+//!
+//! A reference to a style that is not defined uses the less specific levels of its key, and the
+//! undefined states of a style with a dotted key are inherited from its parent level.
+
+use super::{cross::split, levels, serial, Theme};
+use crate::serial::{
+    ButtonComponent, ButtonStyle, Fallback, PaneGridComponent, PicklistStateComponent,
+    PicklistStyle, ScrollableComponent, TextInputComponent, TextInputStyle,
+};
+
+use std::{borrow::Cow, collections::HashMap};
+
+/// Returns the keys of the styles with a defined parent level, with the key of their parent.
+fn parents<S>(styles: &HashMap<String, S>) -> Vec<(String, String)> {
+    styles
+        .keys()
+        .filter_map(|key| {
+            let parent = levels(key)
+                .skip(1)
+                .find(|level| styles.contains_key(*level))?;
+            Some((key.clone(), parent.to_string()))
+        })
+        .collect()
+}
+
+/// Inherits the undefined states of a style from its parent level.
+/// The states with a fallback rule of their own style keep their rule.
+fn inherit<C>(
+    states: Vec<(&str, &mut C)>,
+    rules: &HashMap<String, Fallback>,
+    parent: &str,
+    undefined: fn(&C) -> bool,
+    inherited: fn(String) -> C,
+) {
+    for (state, component) in states {
+        if undefined(component) && !rules.contains_key(state) {
+            *component = inherited(parent.into());
+        }
+    }
+}
+
+impl serial::Theme {
+    /// Inherits the undefined states of the styles with a dotted key from their parent level.
+    pub(crate) fn cascaded(&self) -> Cow<'_, Self> {
+        let mut theme = Cow::Borrowed(self);
+
+        for (key, parent) in parents(&self.button) {
+            if let Some(style) = theme.to_mut().button.get_mut(&key) {
+                let mut b = style.states().into_owned();
+
+                inherit(
+                    vec![
+                        ("active", &mut b.active),
+                        ("hovered", &mut b.hovered),
+                        ("pressed", &mut b.pressed),
+                        ("disabled", &mut b.disabled),
+                    ],
+                    &b.fallback,
+                    &parent,
+                    |c| matches!(c, ButtonComponent::None),
+                    ButtonComponent::Inherited,
+                );
+
+                *style = ButtonStyle::States(Box::new(b));
+            }
+        }
+
+        for (key, parent) in parents(&self.panegrid) {
+            if let Some(p) = theme.to_mut().panegrid.get_mut(&key) {
+                inherit(
+                    vec![("picked", &mut p.picked), ("hovered", &mut p.hovered)],
+                    &p.fallback,
+                    &parent,
+                    |c| matches!(c, PaneGridComponent::None),
+                    PaneGridComponent::Inherited,
+                );
+            }
+        }
+
+        for (key, parent) in parents(&self.picklist) {
+            if let Some(style) = theme.to_mut().picklist.get_mut(&key) {
+                let mut p = style.states().into_owned();
+
+                inherit(
+                    vec![("active", &mut p.active), ("hovered", &mut p.hovered)],
+                    &p.fallback,
+                    &parent,
+                    |c| matches!(c, PicklistStateComponent::None),
+                    PicklistStateComponent::Inherited,
+                );
+
+                *style = PicklistStyle::States(p);
+            }
+        }
+
+        for (key, parent) in parents(&self.scrollable) {
+            if let Some(s) = theme.to_mut().scrollable.get_mut(&key) {
+                inherit(
+                    vec![
+                        ("active", &mut s.active),
+                        ("hovered", &mut s.hovered),
+                        ("dragging", &mut s.dragging),
+                    ],
+                    &s.fallback,
+                    &parent,
+                    |c| matches!(c, ScrollableComponent::None),
+                    ScrollableComponent::Inherited,
+                );
+            }
+        }
+
+        for (key, parent) in parents(&self.textinput) {
+            if let Some(style) = theme.to_mut().textinput.get_mut(&key) {
+                let mut t = style.states().into_owned();
+
+                inherit(
+                    vec![
+                        ("active", &mut t.active),
+                        ("hovered", &mut t.hovered),
+                        ("focused", &mut t.focused),
+                    ],
+                    &t.fallback,
+                    &parent,
+                    |c| matches!(c, TextInputComponent::None),
+                    TextInputComponent::Inherited,
+                );
+
+                *style = TextInputStyle::States(t);
+            }
+        }
+
+        theme
+    }
+}
+
+impl Theme {
+    /// Gets the style of a category referenced by another style, falling back to the less
+    /// specific levels of its key. A level that is defined but not created yet is not skipped,
+    /// so the reference waits until it is created.
+    pub(crate) fn inherited<'a, T>(
+        &self,
+        category: &str,
+        styles: &'a HashMap<String, T>,
+        reference: &str,
+    ) -> Option<&'a T> {
+        for level in levels(split(reference, category).1) {
+            if let Some(style) = styles.get(level) {
+                return Some(style);
+            }
+
+            if self.pending.contains(&format!("{}:{}", category, level)) {
+                return None;
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+
+    const RED: Color = Color(255, 0, 0, 1.0);
+    const WHITE: Color = Color(255, 255, 255, 1.0);
+    const BLACK: Color = Color(0, 0, 0, 1.0);
+
+    fn parse(styles: &str) -> Theme {
+        let theme: serial::Theme = ron::from_str(&format!(
+            r##"(
+                name: "test",
+                description: "",
+                application: (background_color: "white", text_color: "black"),
+                color: {{ "white": "#ffffff", "black": "#000000", "red": "#ff0000" }},
+                border: {{ "thin": (color: "black", radius: 1.0, width: 1.0) }},
+                {}
+            )"##,
+            styles
+        ))
+        .unwrap();
+
+        Theme::parse(&theme).unwrap()
+    }
+
+    #[test]
+    fn undefined_states() {
+        let theme = parse(
+            r#"
+            fallback: { "button": { "hovered": (from: "active") } },
+            button: {
+                "primary": (
+                    active: (background: "red", text: "white", border: "thin"),
+                    hovered: (background: "white", text: "black", border: "thin"),
+                ),
+                "primary.small": (active: (background: "black", text: "white", border: "thin")),
+            },
+            "#,
+        );
+
+        let button = &theme.button["primary.small"];
+        assert_eq!(button.state[0].background, BLACK);
+        assert_eq!(button.state[1].background, WHITE);
+    }
+
+    #[test]
+    fn own_rules() {
+        let theme = parse(
+            r#"
+            button: {
+                "primary": (
+                    active: (background: "red", text: "white", border: "thin"),
+                    hovered: (background: "white", text: "black", border: "thin"),
+                ),
+                "primary.small": (
+                    active: (background: "black", text: "white", border: "thin"),
+                    fallback: { "hovered": (from: "active") },
+                ),
+            },
+            "#,
+        );
+
+        assert_eq!(theme.button["primary.small"].state[1].background, BLACK);
+    }
+
+    #[test]
+    fn inherited_levels() {
+        let theme = parse(
+            r#"
+            button: {
+                "primary": (active: (background: "red", text: "white", border: "thin")),
+                "primary.small": (active: (background: "black", text: "white", border: "thin")),
+                "inherited": (active: "primary.small.icon"),
+                "extended": (active: (from: "primary.small", text: Some("red"))),
+            },
+            container: {
+                "card": (color: "white", border: "thin"),
+                "panel": "card.wide",
+            },
+            "#,
+        );
+
+        assert_eq!(theme.button["inherited"].state[0].background, BLACK);
+        assert_eq!(theme.button["extended"].state[0].background, BLACK);
+        assert_eq!(theme.button["extended"].state[0].text, RED);
+        assert_eq!(theme.container["panel"].color, WHITE);
+    }
+
+    #[test]
+    fn getters() {
+        let theme = parse(
+            r#"
+            button: {
+                "primary": (active: (background: "red", text: "white", border: "thin")),
+            },
+            "#,
+        );
+
+        assert!(matches!(
+            theme.get_button("primary.small"),
+            iced::theme::Button::Custom(_)
+        ));
+        assert!(matches!(
+            theme.get_button("missing"),
+            iced::theme::Button::Primary
+        ));
+        assert!(matches!(
+            theme.get_container("missing"),
+            iced::theme::Container::Transparent
+        ));
+    }
+}
@@ -0,0 +1,220 @@
+//! Conditional overrides of a theme (e.g. different border widths on each platform).
+//!
+//! A conditional override is a fragment that overrides the entries of the theme when its
+//! condition matches the `Context` the theme is created in. The overrides are applied in
+//! order, so a later override takes precedence over an earlier one. They are applied after the
+//! included fragments and the variant, and before the scopes.
+
+use super::{serial, Fragment};
+use crate::serial::ApplicationOverride;
+
+use serde::{Deserialize, Serialize};
+
+use std::borrow::Cow;
+
+/// Context in which a theme is created, which the conditions are evaluated against.
+/// The default context is the current platform.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Context {
+    /// Operating system (e.g. `"linux"`, `"windows"` or `"macos"`), as in `std::env::consts::OS`.
+    pub os: String,
+
+    /// Operating system family (e.g. `"unix"` or `"windows"`), as in `std::env::consts::FAMILY`.
+    pub family: String,
+}
+
+impl Context {
+    /// Returns the context of the current platform.
+    pub fn current() -> Self {
+        Context {
+            os: std::env::consts::OS.into(),
+            family: std::env::consts::FAMILY.into(),
+        }
+    }
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Self::current()
+    }
+}
+
+/// Condition of a conditional override. All the given fields must match the context.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct Condition {
+    /// Operating system the override applies to.
+    pub os: Option<String>,
+
+    /// Operating system family the override applies to.
+    pub family: Option<String>,
+}
+
+impl Condition {
+    /// Checks if the condition matches the given context.
+    pub fn matches(&self, context: &Context) -> bool {
+        let os = self.os.iter().all(|os| *os == context.os);
+        let family = self.family.iter().all(|f| *f == context.family);
+
+        os && family
+    }
+}
+
+/// Fragment that overrides the entries of a theme when its condition matches.
+/// The fragments included by the override are not loaded.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Conditional {
+    /// Condition of the override.
+    pub when: Condition,
+
+    /// Entries that are overridden.
+    pub apply: Fragment,
+
+    /// Fields of the application section that are overridden.
+    #[serde(default)]
+    pub application: ApplicationOverride,
+}
+
+impl serial::Theme {
+    /// Applies the conditional overrides that match the given context.
+    /// The returned theme has no conditional overrides, so it can be created in any context.
+    pub fn conditioned(&self, context: &Context) -> Cow<'_, Self> {
+        if self.conditions.is_empty() {
+            return Cow::Borrowed(self);
+        }
+
+        let mut theme = self.clone();
+        let conditions = core::mem::take(&mut theme.conditions);
+
+        for conditional in conditions {
+            if conditional.when.matches(context) {
+                theme.apply(conditional.apply);
+                theme.application.apply(conditional.application);
+            }
+        }
+
+        Cow::Owned(theme)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Color, Options, Theme};
+
+    const RED: Color = Color(255, 0, 0, 1.0);
+    const GREEN: Color = Color(0, 255, 0, 1.0);
+    const BLUE: Color = Color(0, 0, 255, 1.0);
+    const WHITE: Color = Color(255, 255, 255, 1.0);
+
+    fn linux() -> Context {
+        Context {
+            os: "linux".into(),
+            family: "unix".into(),
+        }
+    }
+
+    fn windows() -> Context {
+        Context {
+            os: "windows".into(),
+            family: "windows".into(),
+        }
+    }
+
+    fn theme() -> serial::Theme {
+        ron::from_str(
+            r##"(
+                name: "test",
+                description: "",
+                application: (background_color: "white", text_color: "accent"),
+                color: { "white": "#ffffff", "accent": "#ffffff" },
+                text: { "accent": "accent" },
+                variants: { "dark": (color: { "accent": "#00ff00" }) },
+                scopes: { "sidebar": (color: { "accent": "#0000ff" }) },
+                conditions: [
+                    (when: (os: Some("windows")), apply: (color: { "accent": "#ff0000" })),
+                ],
+            )"##,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn platforms() {
+        let linux = Theme::parse_with(&theme(), &Options::new().context(linux())).unwrap();
+        let windows = Theme::parse_with(&theme(), &Options::new().context(windows())).unwrap();
+
+        assert_eq!(linux.text["accent"], WHITE);
+        assert_eq!(windows.text["accent"], RED);
+    }
+
+    #[test]
+    fn families() {
+        let condition = Condition {
+            os: None,
+            family: Some("unix".into()),
+        };
+
+        assert!(condition.matches(&linux()));
+        assert!(!condition.matches(&windows()));
+        assert!(Condition::default().matches(&windows()));
+    }
+
+    #[test]
+    fn precedence() {
+        let dark = |context| Options::new().context(context).variant("dark");
+
+        let linux = Theme::parse_with(&theme(), &dark(linux())).unwrap();
+        let windows = Theme::parse_with(&theme(), &dark(windows())).unwrap();
+
+        assert_eq!(linux.text["accent"], GREEN);
+        assert_eq!(windows.text["accent"], RED);
+        assert_eq!(windows.scoped("sidebar").text["accent"], BLUE);
+    }
+
+    #[test]
+    fn application() {
+        let mut serial = theme();
+        serial.application.font = Some("Inter.ttf".into());
+        serial.application.text_size = Some(16.0.into());
+        serial.conditions.push(
+            ron::from_str(
+                r#"(
+                    when: (family: Some("windows")),
+                    apply: (),
+                    application: (
+                        text_color: Some("white"),
+                        font: Some("Segoe.ttf"),
+                        text_size: Some(14.0),
+                    ),
+                )"#,
+            )
+            .unwrap(),
+        );
+
+        let linux = Theme::parse_with(&serial, &Options::new().context(linux())).unwrap();
+        let windows = Theme::parse_with(&serial, &Options::new().context(windows())).unwrap();
+
+        assert_eq!(linux.application.font.as_deref(), Some("Inter.ttf"));
+        assert_eq!(linux.application.text_size, Some(16));
+        assert_eq!(linux.application.text_color, WHITE);
+
+        assert_eq!(windows.application.font.as_deref(), Some("Segoe.ttf"));
+        assert_eq!(windows.application.text_size, Some(14));
+        assert_eq!(windows.application.text_color, WHITE);
+        assert_eq!(windows.text["accent"], RED);
+    }
+
+    #[test]
+    fn switch() {
+        let serial = theme();
+        let mut theme = Theme::parse_with(&serial, &Options::new().context(windows())).unwrap();
+
+        theme.switch(&serial, &Options::new().variant("dark")).unwrap();
+        assert_eq!(theme.text["accent"], RED);
+
+        theme.switch(&serial, &Options::new()).unwrap();
+        assert_eq!(theme.context, windows());
+        assert_eq!(theme.text["accent"], RED);
+    }
+}
@@ -0,0 +1,533 @@
+//! Inheritance between styles of different categories.
+//!
+//! A reference qualified with a category (e.g. `"container:card"`) copies the fields that
+//! are shared between both categories. The fields that are specific to a category
+//! (e.g. the handle of a picklist) must be overridden.
+
+use super::{cascade, serial, Theme};
+use crate::{
+    serial::{BorderValue, ContainerComponent},
+    Border, Color, Error,
+};
+
+/// Fields shared between the styles of different categories.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Shared {
+    /// Background color.
+    pub background: Option<Color>,
+
+    /// Text color.
+    pub text: Option<Color>,
+
+    /// Border theme.
+    pub border: Option<Border>,
+}
+
+/// Splits a style reference into its category and name.
+/// References that are not qualified or are qualified with the given category have no category.
+/// The separator of a namespaced name (e.g. `"plugin-x::card"`) does not qualify it.
+pub(crate) fn split<'a>(reference: &'a str, category: &str) -> (Option<&'a str>, &'a str) {
+    match reference.split_once(':') {
+        Some((_, name)) if name.starts_with(':') => (None, reference),
+        Some((c, name)) if c == category => (None, name),
+        Some((c, name)) => (Some(c), name),
+        _ => (None, reference),
+    }
+}
+
+/// Returns the names of the fields overridden by an inherited style.
+pub(crate) fn overrides(fields: &[(&'static str, bool)]) -> Vec<&'static str> {
+    fields
+        .iter()
+        .filter(|(_, overridden)| *overridden)
+        .map(|(field, _)| *field)
+        .collect()
+}
+
+/// Returns the names of the states of a category, in the order of their `state` array.
+fn states(category: &str) -> &'static [&'static str] {
+    match category {
+        "button" => &["active", "hovered", "pressed", "disabled"],
+        "panegrid" => &["picked", "hovered"],
+        "picklist" => &["active", "hovered"],
+        "scrollable" => &["active", "hovered", "dragging"],
+        "textinput" => &["active", "hovered", "focused"],
+        _ => &["active"],
+    }
+}
+
+/// Returns the name of a state of a category.
+pub(crate) fn state(category: &str, index: usize) -> &'static str {
+    states(category).get(index).copied().unwrap_or("active")
+}
+
+/// Returns the index of a state of a category.
+pub(crate) fn index(category: &str, state: &str) -> Option<usize> {
+    states(category).iter().position(|s| *s == state)
+}
+
+/// Returns the shared fields provided by the styles of a category.
+/// Returns `None` if the category does not exist.
+fn provided(category: &str) -> Option<&'static [&'static str]> {
+    match category {
+        "button" | "container" | "picklist" | "textinput" | "tooltip" => {
+            Some(&["background", "text", "border"])
+        }
+        "scrollable" => Some(&["background", "border"]),
+        "progressbar" => Some(&["background"]),
+        "border" | "panegrid" => Some(&[]),
+        _ => None,
+    }
+}
+
+/// Returns the required fields of the styles of a category, with the shared field they copy.
+fn required(category: &str) -> &'static [(&'static str, Option<&'static str>)] {
+    match category {
+        "border" => &[("color", None), ("radius", None), ("width", None)],
+        "button" | "tooltip" => &[
+            ("background", Some("background")),
+            ("text", Some("text")),
+            ("border", Some("border")),
+        ],
+        "container" => &[("color", Some("background")), ("border", Some("border"))],
+        "menu" => &[
+            ("background", Some("background")),
+            ("text", Some("text")),
+            ("border", Some("border")),
+            ("sbackground", None),
+            ("stext", None),
+        ],
+        "panegrid" => &[("color", None), ("width", None)],
+        "picklist" => &[
+            ("background", Some("background")),
+            ("text", Some("text")),
+            ("placeholder", None),
+            ("border", Some("border")),
+            ("handle", None),
+        ],
+        "progressbar" => &[
+            ("background", Some("background")),
+            ("bar", None),
+            ("radius", None),
+        ],
+        "scrollable" => &[
+            ("color", Some("background")),
+            ("border", Some("border")),
+            ("scolor", None),
+            ("sborder", None),
+        ],
+        "textinput" => &[
+            ("background", Some("background")),
+            ("border", Some("border")),
+        ],
+        _ => &[],
+    }
+}
+
+/// Checks if a style provides a shared field.
+/// Containers only provide a text color if it is defined or inherited.
+fn provides(theme: &serial::Theme, category: &str, name: &str, field: &str, depth: usize) -> bool {
+    if !provided(category).is_some_and(|fields| fields.contains(&field)) {
+        return false;
+    }
+
+    if (category != "container") || (field != "text") {
+        return true;
+    }
+
+    // Circular inheritance is reported when the style cannot be created.
+    if depth > theme.container.len() {
+        return true;
+    }
+
+    match cascade(&theme.container, name) {
+        Some(ContainerComponent::Defined(container)) => container.text.is_some(),
+
+        Some(container) => match container.parent() {
+            Some((_, overridden)) if overridden.contains(&"text") => true,
+
+            Some((reference, _)) => match split(reference, "container") {
+                (Some(category), name) => provides(theme, category, name, field, depth + 1),
+                (_, name) => provides(theme, "container", name, field, depth + 1),
+            },
+
+            _ => false,
+        },
+
+        // Missing styles are reported when the style cannot be created.
+        _ => true,
+    }
+}
+
+/// Checks that a style is compatible with the style of another category it inherits from.
+fn check(
+    theme: &serial::Theme,
+    category: &'static str,
+    fields: &str,
+    name: &str,
+    parent: Option<(&str, Vec<&'static str>)>,
+) -> Result<(), Error> {
+    let (reference, overridden) = match parent {
+        Some(parent) => parent,
+        _ => return Ok(()),
+    };
+
+    let (source, key) = match split(reference, category) {
+        (Some(source), key) => (source, key),
+        _ => return Ok(()),
+    };
+
+    if provided(source).is_none() {
+        return Err(Error::UnknownCategory {
+            category,
+            name: name.into(),
+            reference: reference.into(),
+        });
+    }
+
+    for (field, shared) in required(fields) {
+        let compatible = overridden.contains(field)
+            || shared.is_some_and(|shared| provides(theme, source, key, shared, 0));
+
+        if !compatible {
+            return Err(Error::Incompatible {
+                category,
+                name: name.into(),
+                reference: reference.into(),
+                field,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that all the styles that inherit from other categories are compatible with them.
+pub(crate) fn validate(theme: &serial::Theme) -> Result<(), Error> {
+    for (name, border) in &theme.border {
+        check(theme, "border", "border", name, border.parent())?;
+    }
+
+    for (name, button) in &theme.button {
+        let button = button.states();
+
+        for state in [
+            &button.active,
+            &button.hovered,
+            &button.pressed,
+            &button.disabled,
+        ] {
+            check(theme, "button", "button", name, state.parent())?;
+        }
+    }
+
+    for (name, container) in &theme.container {
+        check(theme, "container", "container", name, container.parent())?;
+    }
+
+    for (name, panegrid) in &theme.panegrid {
+        for state in [&panegrid.picked, &panegrid.hovered] {
+            check(theme, "panegrid", "panegrid", name, state.parent())?;
+        }
+    }
+
+    for (name, picklist) in &theme.picklist {
+        let picklist = picklist.states();
+
+        for state in [&picklist.active, &picklist.hovered] {
+            check(theme, "picklist", "picklist", name, state.parent())?;
+        }
+
+        check(theme, "picklist", "menu", name, picklist.menu.parent())?;
+    }
+
+    for (name, progressbar) in &theme.progressbar {
+        check(
+            theme,
+            "progressbar",
+            "progressbar",
+            name,
+            progressbar.parent(),
+        )?;
+    }
+
+    for (name, scrollable) in &theme.scrollable {
+        let horizontal = scrollable
+            .horizontal
+            .iter()
+            .flat_map(|axis| [&axis.active, &axis.hovered, &axis.dragging]);
+
+        for state in [
+            &scrollable.active,
+            &scrollable.hovered,
+            &scrollable.dragging,
+        ]
+        .into_iter()
+        .chain(horizontal)
+        {
+            check(theme, "scrollable", "scrollable", name, state.parent())?;
+        }
+    }
+
+    for (name, textinput) in &theme.textinput {
+        let textinput = textinput.states();
+
+        for state in [&textinput.active, &textinput.hovered, &textinput.focused] {
+            check(theme, "textinput", "textinput", name, state.parent())?;
+        }
+    }
+
+    for (name, tooltip) in &theme.tooltip {
+        check(theme, "tooltip", "tooltip", name, tooltip.parent())?;
+    }
+
+    Ok(())
+}
+
+/// Gets a color field from its overriding key or from the shared fields.
+pub(crate) fn color(
+    theme: &Theme,
+    key: &Option<String>,
+    shared: Option<Color>,
+) -> Result<Color, ()> {
+    match key {
+        Some(key) => theme.color_key(key),
+        _ => shared.ok_or(()),
+    }
+}
+
+/// Gets a border field from its overriding key or from the shared fields.
+pub(crate) fn border(
+    theme: &Theme,
+    key: &Option<BorderValue>,
+    shared: Option<Border>,
+) -> Result<Border, ()> {
+    match key {
+        Some(key) => theme.border_value(key),
+        _ => shared.ok_or(()),
+    }
+}
+
+impl Theme {
+    /// Gets the shared fields of a state of a style, mapping the state by name.
+    /// Styles without the given state share their active state.
+    pub(crate) fn shared(&self, category: &str, name: &str, state: &str) -> Result<Shared, ()> {
+        let i = index(category, state).unwrap_or(0);
+
+        let shared = match category {
+            "border" => self
+                .inherited("border", &self.border, name)
+                .map(|_| Shared::default()),
+
+            "button" => self
+                .inherited("button", &self.button, name)
+                .map(|button| Shared {
+                    background: Some(button.state[i].background),
+                    text: Some(button.state[i].text),
+                    border: Some(button.state[i].border),
+                }),
+
+            "container" => self
+                .inherited("container", &self.container, name)
+                .map(|container| Shared {
+                    background: Some(container.color),
+                    text: container.text,
+                    border: Some(container.border),
+                }),
+
+            "panegrid" => self
+                .inherited("panegrid", &self.panegrid, name)
+                .map(|_| Shared::default()),
+
+            "picklist" => self
+                .inherited("picklist", &self.picklist, name)
+                .map(|picklist| Shared {
+                    background: Some(picklist.state[i].background),
+                    text: Some(picklist.state[i].text),
+                    border: Some(picklist.state[i].border),
+                }),
+
+            "progressbar" => {
+                self.inherited("progressbar", &self.progressbar, name)
+                    .map(|progressbar| Shared {
+                        background: Some(progressbar.background),
+                        ..Shared::default()
+                    })
+            }
+
+            "scrollable" => {
+                self.inherited("scrollable", &self.scrollable, name)
+                    .map(|scrollable| Shared {
+                        background: Some(scrollable.state[i].color),
+                        border: Some(scrollable.state[i].border),
+                        ..Shared::default()
+                    })
+            }
+
+            "textinput" => self
+                .inherited("textinput", &self.textinput, name)
+                .map(|textinput| Shared {
+                    background: Some(textinput.state[i].background),
+                    text: Some(textinput.colors[1]),
+                    border: Some(textinput.state[i].border),
+                }),
+
+            "tooltip" => self
+                .inherited("tooltip", &self.tooltip, name)
+                .map(|tooltip| Shared {
+                    background: Some(tooltip.background),
+                    text: Some(tooltip.text),
+                    border: Some(tooltip.border),
+                }),
+
+            _ => None,
+        };
+
+        shared.ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: Color = Color(255, 0, 0, 1.0);
+    const WHITE: Color = Color(255, 255, 255, 1.0);
+    const BLACK: Color = Color(0, 0, 0, 1.0);
+
+    fn parse(styles: &str) -> Result<Theme, Error> {
+        let theme: serial::Theme = ron::from_str(&format!(
+            r##"(
+                name: "test",
+                description: "",
+                application: (background_color: "white", text_color: "black"),
+                color: {{ "white": "#ffffff", "black": "#000000", "red": "#ff0000" }},
+                border: {{ "thin": (color: "black", radius: 1.0, width: 1.0) }},
+                container: {{
+                    "card": (color: "white", border: "thin"),
+                    "card.text": (color: "red", border: "thin", text: Some("white")),
+                }},
+                {}
+            )"##,
+            styles
+        ))
+        .unwrap();
+
+        Theme::parse(&theme)
+    }
+
+    #[test]
+    fn references() {
+        assert_eq!(split("primary", "button"), (None, "primary"));
+        assert_eq!(split("button:primary", "button"), (None, "primary"));
+        assert_eq!(
+            split("container:card", "button"),
+            (Some("container"), "card")
+        );
+        assert_eq!(split("plugin::card", "button"), (None, "plugin::card"));
+        assert_eq!(
+            split("container:plugin::card", "button"),
+            (Some("container"), "plugin::card")
+        );
+    }
+
+    #[test]
+    fn copies() {
+        let theme = parse(
+            r#"
+            button: { "primary": (active: (background: "red", text: "white", border: "thin")) },
+            tooltip: {
+                "button": "button:primary",
+                "container": "container:card.text",
+                "overridden": (from: "container:card", text: Some("red")),
+            },
+            "#,
+        )
+        .unwrap();
+
+        let button = theme.tooltip["button"];
+        assert_eq!((button.background, button.text), (RED, WHITE));
+
+        let container = theme.tooltip["container"];
+        assert_eq!((container.background, container.text), (RED, WHITE));
+
+        let overridden = theme.tooltip["overridden"];
+        assert_eq!((overridden.background, overridden.text), (WHITE, RED));
+        assert_eq!(overridden.border.color, BLACK);
+    }
+
+    #[test]
+    fn states() {
+        let theme = parse(
+            r#"
+            picklist: {
+                "list": (
+                    active: (
+                        background: "white",
+                        text: "black",
+                        placeholder: "black",
+                        border: "thin",
+                        handle: "black",
+                    ),
+                    hovered: (
+                        background: "red",
+                        text: "white",
+                        placeholder: "black",
+                        border: "thin",
+                        handle: "black",
+                    ),
+                    menu: (
+                        background: "white",
+                        text: "black",
+                        border: "thin",
+                        sbackground: "red",
+                        stext: "white",
+                    ),
+                ),
+            },
+            button: { "copy": (active: "picklist:list", hovered: "picklist:list") },
+            "#,
+        )
+        .unwrap();
+
+        // The states of the parent are mapped by name.
+        let button = &theme.button["copy"];
+        assert_eq!(
+            (button.state[0].background, button.state[0].text),
+            (WHITE, BLACK)
+        );
+        assert_eq!(
+            (button.state[1].background, button.state[1].text),
+            (RED, WHITE)
+        );
+    }
+
+    #[test]
+    fn incompatible() {
+        let error = parse(r#"tooltip: { "tip": "container:card" }"#).unwrap_err();
+
+        assert_eq!(
+            error,
+            Error::Incompatible {
+                category: "tooltip",
+                name: "tip".into(),
+                reference: "container:card".into(),
+                field: "text",
+            }
+        );
+    }
+
+    #[test]
+    fn unknown_categories() {
+        let error = parse(r#"tooltip: { "tip": "widget:card" }"#).unwrap_err();
+
+        assert_eq!(
+            error,
+            Error::UnknownCategory {
+                category: "tooltip",
+                name: "tip".into(),
+                reference: "widget:card".into(),
+            }
+        );
+    }
+}
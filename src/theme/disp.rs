@@ -9,6 +9,19 @@ impl core::fmt::Display for Theme {
         string += &format!("Theme \"{}\"\n", self.name);
         string += &format!("  {}\n", self.description);
 
+        // Display the application.
+        string += "|- Application\n";
+        string += &format!("| |- Background: {}\n", self.application.background_color);
+        string += &format!("| |- Text color: {}\n", self.application.text_color);
+
+        if let Some(size) = self.application.text_size {
+            string += &format!("| |- Text size:  {}\n", size);
+        }
+
+        if let Some(font) = &self.application.font {
+            string += &format!("| |- Font:       \"{}\"\n", font);
+        }
+
         // Display the colors.
         string += "|- Colors\n";
 
@@ -57,12 +70,25 @@ impl core::fmt::Display for Theme {
         for (name, container) in &self.container {
             string += &format!("| |- \"{}\"\n", name);
             string += &format!("| | |- Color: {}\n", container.color);
+            if let Some(text) = container.text {
+                string += &format!("| | |- Text color: {}\n", text);
+            }
             string += "| | |- Border\n";
             string += &format!("| |   |- Color: {}\n", container.border.color);
             string += &format!("| |   |- Radius: {:.3}\n", container.border.radius);
             string += &format!("| |   |- Width:  {:.3}\n", container.border.width);
         }
 
+        // Display the palette.
+        if let Some(palette) = &self.palette {
+            string += "|- Palette\n";
+            string += &format!("| |- Background: {}\n", palette.background);
+            string += &format!("| |- Text:       {}\n", palette.text);
+            string += &format!("| |- Primary:    {}\n", palette.primary);
+            string += &format!("| |- Success:    {}\n", palette.success);
+            string += &format!("| |- Danger:     {}\n", palette.danger);
+        }
+
         // Display the pick list.
         string += "|- Pane Grids\n";
 
@@ -156,46 +182,37 @@ impl core::fmt::Display for Theme {
 
             string += &format!("| |- \"{}\"\n", name);
 
-            for state in 0..3 {
-                string += &format!("| | |- {}\n", STATE[state]);
-                string += &format!(
-                    "| | | |- Scrollbar color: {}\n",
-                    scrollable.state[state].color
-                );
-                string += "| | | |- Scrollbar border:\n";
-                string += &format!(
-                    "| | |   |- Color: {}\n",
-                    scrollable.state[state].border.color
-                );
-                string += &format!(
-                    "| | |   |- Radius: {:.3}\n",
-                    scrollable.state[state].border.radius
-                );
-                string += &format!(
-                    "| | |   |- Width:  {:.3}\n",
-                    scrollable.state[state].border.width
-                );
-
-                string += &format!(
-                    "| | | |- Scroller color: {}\n",
-                    scrollable.state[state].scolor
-                );
-                string += "| | | |- Scroller border:\n";
-                string += &format!(
-                    "| | |   |- Color: {}\n",
-                    scrollable.state[state].sborder.color
-                );
-                string += &format!(
-                    "| | |   |- Radius: {:.3}\n",
-                    scrollable.state[state].sborder.radius
-                );
-                string += &format!(
-                    "| | |   |- Width:  {:.3}\n",
-                    scrollable.state[state].sborder.width
-                );
+            for (axis, states) in [
+                ("Vertical", &scrollable.state),
+                ("Horizontal", &scrollable.horizontal),
+            ] {
+                string += &format!("| | |- {}\n", axis);
+
+                for state in 0..3 {
+                    string += &format!("| | | |- {}\n", STATE[state]);
+                    string += &format!("| | | | |- Scrollbar color: {}\n", states[state].color);
+                    string += "| | | | |- Scrollbar border:\n";
+                    string += &format!("| | | |   |- Color: {}\n", states[state].border.color);
+                    string += &format!("| | | |   |- Radius: {:.3}\n", states[state].border.radius);
+                    string += &format!("| | | |   |- Width:  {:.3}\n", states[state].border.width);
+
+                    string += &format!("| | | | |- Scroller color: {}\n", states[state].scolor);
+                    string += "| | | | |- Scroller border:\n";
+                    string += &format!("| | | |   |- Color: {}\n", states[state].sborder.color);
+                    string +=
+                        &format!("| | | |   |- Radius: {:.3}\n", states[state].sborder.radius);
+                    string += &format!("| | | |   |- Width:  {:.3}\n", states[state].sborder.width);
+                }
             }
         }
 
+        // Display the text styles.
+        string += "|- Texts\n";
+
+        for (name, color) in &self.text {
+            string += &format!("| |- \"{}\": {}\n", name, color);
+        }
+
         // Display the text input.
         string += "|- Scrollbars\n";
 
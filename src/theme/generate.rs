@@ -0,0 +1,384 @@
+//! Generation of a complete theme from an `iced` palette.
+//! The generated styles match the built-in `iced` stylesheets.
+
+use super::{serial, Theme};
+use crate::{serial::*, Color};
+
+use iced::theme::palette::{Extended, Pair};
+
+use std::collections::HashMap;
+
+impl serial::Theme {
+    /// Generates a &serialized theme from an `iced` palette.
+    ///
+    /// The colors of the extended palette are named after their group and level
+    /// (e.g. `"primary"`, `"primary-weak"`, `"primary-strong"`), and the text color
+    /// of each pair is named after the pair with a `"-text"` suffix.
+    pub fn from_palette(palette: iced::theme::Palette) -> Self {
+        let extended = Extended::generate(palette);
+
+        // Name all the colors of the extended palette.
+        let mut color = HashMap::new();
+
+        let groups = [
+            (
+                "background",
+                [
+                    extended.background.base,
+                    extended.background.weak,
+                    extended.background.strong,
+                ],
+            ),
+            (
+                "primary",
+                [
+                    extended.primary.base,
+                    extended.primary.weak,
+                    extended.primary.strong,
+                ],
+            ),
+            (
+                "secondary",
+                [
+                    extended.secondary.base,
+                    extended.secondary.weak,
+                    extended.secondary.strong,
+                ],
+            ),
+            (
+                "success",
+                [
+                    extended.success.base,
+                    extended.success.weak,
+                    extended.success.strong,
+                ],
+            ),
+            (
+                "danger",
+                [
+                    extended.danger.base,
+                    extended.danger.weak,
+                    extended.danger.strong,
+                ],
+            ),
+        ];
+
+        for (group, pairs) in groups {
+            for (level, pair) in ["", "-weak", "-strong"].iter().zip(pairs) {
+                let Pair { color: c, text } = pair;
+                color.insert(format!("{}{}", group, level), Color::from(c));
+                color.insert(format!("{}{}-text", group, level), Color::from(text));
+            }
+        }
+
+        color.insert("text".into(), Color::from(palette.text));
+        color.insert("transparent".into(), Color::TRANSPARENT);
+
+        // Faded colors of the disabled buttons.
+        for name in ["primary-strong", "secondary", "success", "danger"] {
+            for key in [name.to_string(), format!("{}-text", name)] {
+                let Color(r, g, b, a) = color[&key];
+                color.insert(format!("{}-disabled", key), Color(r, g, b, a * 0.5));
+            }
+        }
+
+        let text_disabled = {
+            let Color(r, g, b, a) = color["background-text"];
+            Color(r, g, b, a * 0.5)
+        };
+        color.insert("background-text-disabled".into(), text_disabled);
+
+        // Borders.
+        let border = [
+            ("none", "transparent", 0.0, 0.0),
+            ("default", "transparent", 0.0, 2.0),
+            ("strong", "background-strong", 1.0, 2.0),
+            ("text", "background-text", 1.0, 2.0),
+            ("primary", "primary-strong", 1.0, 2.0),
+            ("menu", "background-strong", 1.0, 0.0),
+        ]
+        .into_iter()
+        .map(|(name, color, width, radius)| {
+            (
+                name.to_string(),
+                BorderComponent::Defined(Border {
+                    color: color.into(),
+                    radius: radius.into(),
+                    width: width.into(),
+                }),
+            )
+        })
+        .collect();
+
+        // Buttons.
+        let button = [
+            ("primary", "primary-strong", "primary"),
+            ("secondary", "secondary", "background-strong"),
+            ("positive", "success", "success-strong"),
+            ("destructive", "danger", "danger-strong"),
+        ]
+        .into_iter()
+        .map(|(name, active, hovered)| {
+            (
+                name.to_string(),
+                generate_button(active, hovered, &format!("{}-text", active), false),
+            )
+        })
+        .chain(std::iter::once((
+            "text".to_string(),
+            generate_button("transparent", "transparent", "background-text", true),
+        )))
+        .collect();
+
+        // Containers.
+        let container = [
+            ("transparent", "transparent", "none"),
+            ("box", "background-weak", "default"),
+        ]
+        .into_iter()
+        .map(|(name, color, border)| {
+            (
+                name.to_string(),
+                ContainerComponent::Defined(Container {
+                    color: color.into(),
+                    border: border.into(),
+                    text: None,
+                }),
+            )
+        })
+        .collect();
+
+        // Pane grids.
+        let panegrid = HashMap::from([(
+            "default".to_string(),
+            PaneGrid {
+                picked: PaneGridComponent::Defined(PaneGridState {
+                    color: "primary-strong".into(),
+                    width: 2.0.into(),
+                }),
+                hovered: PaneGridComponent::Defined(PaneGridState {
+                    color: "primary".into(),
+                    width: 2.0.into(),
+                }),
+                fallback: HashMap::new(),
+            },
+        )]);
+
+        // Picklists.
+        let picklist_state = |border: &str| {
+            PicklistStateComponent::Defined(PicklistState {
+                background: "background-weak".into(),
+                text: "background-weak-text".into(),
+                placeholder: "background-strong".into(),
+                border: border.into(),
+                handle: "background-weak-text".into(),
+            })
+        };
+
+        let picklist = HashMap::from([(
+            "default".to_string(),
+            PicklistStyle::States(Picklist {
+                active: picklist_state("strong"),
+                hovered: picklist_state("primary"),
+                menu: PicklistMenuComponent::Defined(PicklistMenu {
+                    background: "background-weak".into(),
+                    text: "background-weak-text".into(),
+                    border: "menu".into(),
+                    sbackground: "primary-strong".into(),
+                    stext: "primary-strong-text".into(),
+                }),
+                fallback: HashMap::new(),
+            }),
+        )]);
+
+        // Progress bars.
+        let progressbar = ["primary", "success", "danger"]
+            .into_iter()
+            .map(|name| {
+                (
+                    name.to_string(),
+                    ProgressBarComponent::Defined(ProgressBar {
+                        background: "background-strong".into(),
+                        bar: name.into(),
+                        radius: 2.0.into(),
+                    }),
+                )
+            })
+            .collect();
+
+        // Scrollables.
+        let scrollable_state = |scolor: &str| {
+            ScrollableComponent::Defined(ScrollableState {
+                color: "background-weak".into(),
+                border: "default".into(),
+                scolor: scolor.into(),
+                sborder: "default".into(),
+            })
+        };
+
+        let scrollable = HashMap::from([(
+            "default".to_string(),
+            Scrollable {
+                active: scrollable_state("background-strong"),
+                hovered: scrollable_state("primary-strong"),
+                dragging: scrollable_state("primary-strong"),
+                horizontal: None,
+                fallback: HashMap::new(),
+            },
+        )]);
+
+        // Text styles.
+        let text = ["text", "primary", "success", "danger"]
+            .into_iter()
+            .map(|name| (name.to_string(), name.to_string()))
+            .collect();
+
+        // Text inputs.
+        let textinput_state = |border: &str| {
+            TextInputComponent::Defined(TextInputState {
+                background: "background".into(),
+                border: border.into(),
+            })
+        };
+
+        let textinput = HashMap::from([(
+            "default".to_string(),
+            TextInputStyle::States(TextInput {
+                active: textinput_state("strong"),
+                hovered: textinput_state("text"),
+                focused: textinput_state("primary"),
+                placeholder: "background-strong".into(),
+                value: "background-text".into(),
+                selection: "primary-weak".into(),
+                fallback: HashMap::new(),
+            }),
+        )]);
+
+        // Tooltips.
+        let tooltip = HashMap::from([(
+            "default".to_string(),
+            TooltipComponent::Defined(Tooltip {
+                background: "background-weak".into(),
+                text: "background-weak-text".into(),
+                border: "default".into(),
+            }),
+        )]);
+
+        Self {
+            name: String::new(),
+            description: String::new(),
+            application: Application {
+                background_color: "background".into(),
+                text_color: "text".into(),
+                text_size: None,
+                font: None,
+                defaults: Defaults {
+                    button: Some("primary".into()),
+                    container: Some("transparent".into()),
+                    panegrid: Some("default".into()),
+                    picklist: Some("default".into()),
+                    progressbar: Some("primary".into()),
+                    scrollable: Some("default".into()),
+                    textinput: Some("default".into()),
+                    ..Defaults::default()
+                },
+            },
+            aliases: HashMap::new(),
+            include: Vec::new(),
+            border,
+            button,
+            color,
+            container,
+            fallback: HashMap::new(),
+            palette: Some(Palette {
+                background: "background".into(),
+                text: "text".into(),
+                primary: "primary".into(),
+                success: "success".into(),
+                danger: "danger".into(),
+            }),
+            panegrid,
+            picklist,
+            progressbar,
+            scrollable,
+            scopes: HashMap::new(),
+            text,
+            textinput,
+            tokens: HashMap::new(),
+            tooltip,
+            variants: HashMap::new(),
+            conditions: Vec::new(),
+        }
+    }
+}
+
+/// Generates a button with the given colors.
+/// The pressed state is the active state, and the disabled state fades the active state.
+fn generate_button(active: &str, hovered: &str, text: &str, text_only: bool) -> ButtonStyle {
+    let state = |background: &str, text: &str| {
+        ButtonComponent::Defined(ButtonState {
+            background: background.into(),
+            text: text.into(),
+            border: "default".into(),
+        })
+    };
+
+    let disabled = match text_only {
+        true => state(active, &format!("{}-disabled", text)),
+        _ => state(
+            &format!("{}-disabled", active),
+            &format!("{}-disabled", text),
+        ),
+    };
+
+    ButtonStyle::States(Box::new(Button {
+        active: state(active, text),
+        hovered: state(hovered, text),
+        pressed: state(active, text),
+        disabled,
+        fallback: HashMap::new(),
+    }))
+}
+
+impl Theme {
+    /// Generates a theme from an `iced` palette.
+    /// See `serial::Theme::from_palette` for the generated keys.
+    pub fn from_palette(palette: iced::theme::Palette) -> Self {
+        // The generated theme only references its own keys, as the tests check for the built-in
+        // palettes.
+        Theme::parse(&serial::Theme::from_palette(palette))
+            .expect("the generated theme only references its own keys")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iced::theme::Palette;
+
+    /// Checks that the theme generated from a palette is complete and uses its colors.
+    fn generated(palette: Palette) {
+        let serial = serial::Theme::from_palette(palette);
+        let theme = Theme::parse(&serial).unwrap();
+
+        assert_eq!(theme.button.len(), serial.button.len());
+        assert_eq!(theme.container.len(), serial.container.len());
+        assert_eq!(theme.textinput.len(), serial.textinput.len());
+
+        assert_eq!(theme.color["primary"], Color::from(palette.primary));
+        assert_eq!(
+            theme.application.background_color,
+            Color::from(palette.background)
+        );
+    }
+
+    #[test]
+    fn light() {
+        generated(Palette::LIGHT);
+    }
+
+    #[test]
+    fn dark() {
+        generated(Palette::DARK);
+    }
+}
@@ -0,0 +1,413 @@
+//! Themes split across several files.
+//!
+//! A theme can include fragments, which are partial themes that only define some categories.
+//! The fragments are loaded through a `Resolver` and merged in order after the entries of the
+//! main theme. A fragment included more than once (e.g. a shared color palette) is only merged
+//! once, and an entry that is defined more than once is reported as a conflict.
+
+use super::{serial, Theme};
+use crate::{serial::*, Color, Error};
+
+use serde::{Deserialize, Serialize};
+
+use std::collections::{HashMap, HashSet};
+
+/// Partial theme, included by a theme or by another fragment.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Fragment {
+    /// Paths of the fragments included by this fragment.
+    pub include: Vec<String>,
+
+    /// Maps name keys to border themes.
+    pub border: HashMap<String, BorderComponent>,
+
+    // Maps name keys to button themes.
+    pub button: HashMap<String, ButtonStyle>,
+
+    /// Maps name keys to colors.
+    pub color: HashMap<String, Color>,
+
+    /// Maps name keys to containers.
+    pub container: HashMap<String, ContainerComponent>,
+
+    /// Maps category names to the fallback rules of their states.
+    pub fallback: HashMap<String, HashMap<String, Fallback>>,
+
+    /// Maps name keys to pane grids.
+    pub panegrid: HashMap<String, PaneGrid>,
+
+    /// Maps name keys to picklists.
+    pub picklist: HashMap<String, PicklistStyle>,
+
+    /// Maps name keys to progress bar.
+    pub progressbar: HashMap<String, ProgressBarComponent>,
+
+    /// Maps name keys to scrollable.
+    pub scrollable: HashMap<String, Scrollable>,
+
+    /// Maps name keys to text color keys.
+    pub text: HashMap<String, String>,
+
+    /// Maps name keys to text input.
+    pub textinput: HashMap<String, TextInputStyle>,
+
+    /// Maps name keys to numeric tokens.
+    pub tokens: HashMap<String, Number>,
+
+    /// Maps name keys to tooltip.
+    pub tooltip: HashMap<String, TooltipComponent>,
+}
+
+/// Loads the fragments included by a theme (e.g. reading and deserializing a file).
+pub trait Resolver {
+    /// Attempts to load the fragment at the given path.
+    /// The error is a description of the reason why it could not be loaded.
+    fn resolve(&self, path: &str) -> Result<Fragment, String>;
+}
+
+impl<F: Fn(&str) -> Result<Fragment, String>> Resolver for F {
+    fn resolve(&self, path: &str) -> Result<Fragment, String> {
+        self(path)
+    }
+}
+
+/// Maps the category and name of each entry to the fragment that defines it.
+/// The entries of the main theme have no fragment.
+type Origins = HashMap<(&'static str, String), Option<String>>;
+
+/// Merges the entries of a category, reporting the entries that are already defined.
+fn merge<T>(
+    category: &'static str,
+    target: &mut HashMap<String, T>,
+    source: HashMap<String, T>,
+    file: &Option<String>,
+    origins: &mut Origins,
+) -> Result<(), Error> {
+    for (name, entry) in source {
+        if let Some(previous) = origins.get(&(category, name.clone())) {
+            return Err(Error::Conflict {
+                category,
+                name,
+                file: file.clone().unwrap_or_default(),
+                previous: previous.clone(),
+            });
+        }
+
+        origins.insert((category, name.clone()), file.clone());
+        target.insert(name, entry);
+    }
+
+    Ok(())
+}
+
+/// Merges all the categories of a fragment into a theme.
+fn fragment(
+    theme: &mut serial::Theme,
+    fragment: Fragment,
+    file: &Option<String>,
+    origins: &mut Origins,
+) -> Result<(), Error> {
+    merge("border", &mut theme.border, fragment.border, file, origins)?;
+    merge("button", &mut theme.button, fragment.button, file, origins)?;
+    merge("color", &mut theme.color, fragment.color, file, origins)?;
+    merge(
+        "container",
+        &mut theme.container,
+        fragment.container,
+        file,
+        origins,
+    )?;
+    merge(
+        "fallback",
+        &mut theme.fallback,
+        fragment.fallback,
+        file,
+        origins,
+    )?;
+    merge(
+        "panegrid",
+        &mut theme.panegrid,
+        fragment.panegrid,
+        file,
+        origins,
+    )?;
+    merge(
+        "picklist",
+        &mut theme.picklist,
+        fragment.picklist,
+        file,
+        origins,
+    )?;
+    merge(
+        "progressbar",
+        &mut theme.progressbar,
+        fragment.progressbar,
+        file,
+        origins,
+    )?;
+    merge(
+        "scrollable",
+        &mut theme.scrollable,
+        fragment.scrollable,
+        file,
+        origins,
+    )?;
+    merge("text", &mut theme.text, fragment.text, file, origins)?;
+    merge(
+        "textinput",
+        &mut theme.textinput,
+        fragment.textinput,
+        file,
+        origins,
+    )?;
+    merge("token", &mut theme.tokens, fragment.tokens, file, origins)?;
+    merge(
+        "tooltip",
+        &mut theme.tooltip,
+        fragment.tooltip,
+        file,
+        origins,
+    )?;
+
+    Ok(())
+}
+
+/// Loads and merges the fragments at the given paths, and the fragments they include.
+/// `stack` holds the paths of the fragments being included, to detect circular includes, and
+/// `visited` holds the paths of the fragments already merged, which are only merged once.
+fn include(
+    theme: &mut serial::Theme,
+    paths: &[String],
+    resolver: &dyn Resolver,
+    stack: &mut Vec<String>,
+    visited: &mut HashSet<String>,
+    origins: &mut Origins,
+) -> Result<(), Error> {
+    for path in paths {
+        if stack.contains(path) {
+            return Err(Error::Include {
+                file: path.clone(),
+                reason: "circular include".into(),
+            });
+        }
+
+        if !visited.insert(path.clone()) {
+            continue;
+        }
+
+        let mut loaded = resolver.resolve(path).map_err(|reason| Error::Include {
+            file: path.clone(),
+            reason,
+        })?;
+
+        let nested = core::mem::take(&mut loaded.include);
+
+        fragment(theme, loaded, &Some(path.clone()), origins)?;
+
+        stack.push(path.clone());
+        include(theme, &nested, resolver, stack, visited, origins)?;
+        stack.pop();
+    }
+
+    Ok(())
+}
+
+impl serial::Theme {
+    /// Merges the fragments included by this theme, loading them with the given resolver.
+    /// Returns the merged theme and the fragment that defines each entry.
+    fn merged(&self, resolver: &dyn Resolver) -> Result<(Self, Origins), Error> {
+        let mut theme = Self {
+            include: Vec::new(),
+            ..self.clone()
+        };
+
+        // Register the entries of the main theme.
+        let own = Fragment {
+            include: Vec::new(),
+            border: core::mem::take(&mut theme.border),
+            button: core::mem::take(&mut theme.button),
+            color: core::mem::take(&mut theme.color),
+            container: core::mem::take(&mut theme.container),
+            fallback: core::mem::take(&mut theme.fallback),
+            panegrid: core::mem::take(&mut theme.panegrid),
+            picklist: core::mem::take(&mut theme.picklist),
+            progressbar: core::mem::take(&mut theme.progressbar),
+            scrollable: core::mem::take(&mut theme.scrollable),
+            text: core::mem::take(&mut theme.text),
+            textinput: core::mem::take(&mut theme.textinput),
+            tokens: core::mem::take(&mut theme.tokens),
+            tooltip: core::mem::take(&mut theme.tooltip),
+        };
+
+        let mut origins = Origins::new();
+        fragment(&mut theme, own, &None, &mut origins)?;
+
+        include(
+            &mut theme,
+            &self.include,
+            resolver,
+            &mut Vec::new(),
+            &mut HashSet::new(),
+            &mut origins,
+        )?;
+
+        Ok((theme, origins))
+    }
+
+    /// Merges the fragments included by this theme, loading them with the given resolver.
+    pub fn resolve(&self, resolver: &dyn Resolver) -> Result<Self, Error> {
+        self.merged(resolver).map(|(theme, _)| theme)
+    }
+
+    /// Merges the fragments included by this theme and creates the merged theme.
+    /// The errors of the entries defined in a fragment report the path of the fragment.
+    pub(super) fn create_merged(
+        &self,
+        resolver: &dyn Resolver,
+        create: impl FnOnce(&Self) -> Result<Theme, Error>,
+    ) -> Result<Theme, Error> {
+        let (merged, origins) = self.merged(resolver)?;
+
+        create(&merged).map_err(|error| {
+            let file = error
+                .entry()
+                .and_then(|(category, name)| origins.get(&(category, name.to_string())))
+                .cloned()
+                .flatten();
+
+            match file {
+                Some(file) => Error::File {
+                    file,
+                    error: Box::new(error),
+                },
+                _ => error,
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Options;
+
+    fn load(path: &str) -> Result<Fragment, String> {
+        let fragment = match path {
+            "common.ron" => r##"(color: { "white": "#ffffff", "black": "#000000" })"##,
+            "a.ron" => r##"(include: ["common.ron"], text: { "a": "black" })"##,
+            "b.ron" => r##"(include: ["common.ron"], text: { "b": "white" })"##,
+            "loop.ron" => r##"(include: ["loop.ron"])"##,
+            "text.ron" => r##"(text: { "bad": "missing" })"##,
+            "fallback.ron" => r##"(fallback: { "buton": { "hovered": (from: "active") } })"##,
+            _ => return Err("not found".into()),
+        };
+
+        ron::from_str(fragment).map_err(|error| error.to_string())
+    }
+
+    fn parse(theme: &serial::Theme) -> Result<Theme, Error> {
+        Theme::parse_with(theme, &Options::new().resolver(&load))
+    }
+
+    fn theme(include: &[&str]) -> serial::Theme {
+        let mut theme: serial::Theme = ron::from_str(
+            r##"(
+                name: "test",
+                description: "",
+                application: (background_color: "white", text_color: "black"),
+                variants: { "dark": (text: { "a": "white" }) },
+            )"##,
+        )
+        .unwrap();
+
+        theme.include = include.iter().map(|path| path.to_string()).collect();
+        theme
+    }
+
+    #[test]
+    fn shared_fragments() {
+        let theme = parse(&theme(&["a.ron", "b.ron"])).unwrap();
+
+        assert_eq!(theme.text["a"], Color(0, 0, 0, 1.0));
+        assert_eq!(theme.text["b"], Color(255, 255, 255, 1.0));
+    }
+
+    #[test]
+    fn circular_includes() {
+        let error = parse(&theme(&["loop.ron"])).unwrap_err();
+
+        assert_eq!(
+            error,
+            Error::Include {
+                file: "loop.ron".into(),
+                reason: "circular include".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn missing_fragments() {
+        let error = parse(&theme(&["missing.ron"])).unwrap_err();
+
+        assert!(matches!(error, Error::Include { file, .. } if file == "missing.ron"));
+    }
+
+    #[test]
+    fn conflicts() {
+        let mut main = theme(&["common.ron"]);
+        main.color.insert("white".into(), Color(254, 254, 254, 1.0));
+
+        let error = parse(&main).unwrap_err();
+
+        assert_eq!(
+            error,
+            Error::Conflict {
+                category: "color",
+                name: "white".into(),
+                file: "common.ron".into(),
+                previous: None,
+            }
+        );
+    }
+
+    #[test]
+    fn entry_files() {
+        let error = parse(&theme(&["common.ron", "text.ron"])).unwrap_err();
+
+        assert!(matches!(&error, Error::File { file, .. } if file == "text.ron"));
+        assert_eq!(error.entry(), Some(("text", "bad")));
+
+        let error = parse(&theme(&["fallback.ron"])).unwrap_err();
+
+        assert!(matches!(&error, Error::File { file, .. } if file == "fallback.ron"));
+        assert_eq!(error.entry(), Some(("fallback", "buton")));
+    }
+
+    #[test]
+    fn missing_resolver() {
+        let error = Theme::parse(&theme(&["common.ron"])).unwrap_err();
+
+        assert_eq!(
+            error,
+            Error::Include {
+                file: "common.ron".into(),
+                reason: "no resolver was given".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn variants() {
+        let serial = theme(&["a.ron"]);
+        let options = Options::new().resolver(&load);
+
+        let dark = Theme::parse_with(&serial, &options.clone().variant("dark")).unwrap();
+        assert_eq!(dark.text["a"], Color(255, 255, 255, 1.0));
+
+        let mut theme = parse(&serial).unwrap();
+        theme.switch(&serial, &options.variant("dark")).unwrap();
+        assert_eq!(theme.text["a"], Color(255, 255, 255, 1.0));
+        assert_eq!(theme.variant.as_deref(), Some("dark"));
+    }
+}
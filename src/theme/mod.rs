@@ -1,12 +1,26 @@
 //! `Theme` represents a &serializable collection of a theme.
 
+mod alias;
+mod cascade;
+mod condition;
+pub(crate) mod cross;
 mod disp;
+mod generate;
+mod include;
+mod namespace;
+mod options;
+mod scope;
 mod style;
+mod variant;
 pub mod serial;
 
+pub use condition::{Condition, Conditional, Context};
+pub use include::{Fragment, Resolver};
+pub use options::Options;
+
 use crate::*;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Clone, Default, Debug)]
 pub struct Theme {
@@ -33,6 +47,12 @@ pub struct Theme {
     /// Maps name keys to container themes.
     pub container: HashMap<String, Container>,
 
+    /// Maps category names to the fallback rules of their states.
+    pub fallback: HashMap<String, HashMap<String, Fallback>>,
+
+    /// Palette of the built-in `iced` styles.
+    pub palette: Option<Palette>,
+
     /// Maps name keys to panegrid themes.
     pub panegrid: HashMap<String, PaneGrid>,
 
@@ -45,28 +65,123 @@ pub struct Theme {
     /// Maps name keys to scrollable themes.
     pub scrollable: HashMap<String, Scrollable>,
 
+    /// Maps name keys to text colors.
+    pub text: HashMap<String, Color>,
+
     /// Maps name keys to text input themes.
     pub textinput: HashMap<String, TextInput>,
 
+    /// Maps scope names to the themes of the regions of the UI that override this theme.
+    /// See `Theme::scoped` to get the theme of a scope.
+    pub scopes: HashMap<String, Theme>,
+
+    /// Maps name keys to numeric tokens.
+    pub tokens: HashMap<String, f32>,
+
     /// Maps name keys to tooltip themes.
     pub tooltip: HashMap<String, Tooltip>,
+
+    /// Name of the variant of this theme, or `None` for the base theme.
+    pub variant: Option<String>,
+
+    /// Names of the variants available in the &serialized version of this theme.
+    pub variants: Vec<String>,
+
+    /// Warnings found while creating this theme (e.g. styles defined with deprecated keys).
+    pub warnings: Vec<Warning>,
+
+    /// Context the conditional overrides of this theme were evaluated against.
+    /// Switching to another variant evaluates them against the same context.
+    pub context: Context,
+
+    /// Maps namespace names to the fragments registered under them.
+    /// They are registered again when switching to another variant.
+    pub namespaces: HashMap<String, Fragment>,
+
+    /// Aliases applied by the application with `Theme::alias`.
+    /// They are applied again when switching to another variant.
+    pub aliases: HashMap<String, String>,
+
+    /// Qualified keys (e.g. `"button:primary"`) of the styles being created, which are not
+    /// skipped when a reference falls back to the less specific levels of its key.
+    pending: HashSet<String>,
+
+    /// Old keys of the aliases applied to this theme, whose entries are not serialized.
+    renamed: HashSet<String>,
+}
+
+/// Returns the levels of a hierarchical style key, from the most to the least specific
+/// (e.g. `"button.primary.small"`, `"button.primary"` and `"button"`).
+pub(crate) fn levels(key: &str) -> impl Iterator<Item = &str> {
+    core::iter::successors(Some(key), |key| key.rsplit_once('.').map(|(parent, _)| parent))
 }
 
-impl iced::application::StyleSheet for Theme {
-    type Style = iced::Theme;
+/// Gets the style with the given key, falling back to the less specific levels of the key.
+pub(crate) fn cascade<'a, T>(map: &'a HashMap<String, T>, key: &str) -> Option<&'a T> {
+    levels(key).find_map(|key| map.get(key))
+}
+
+/// Returns the qualified keys of the styles of a category.
+fn pending<'a, S>(
+    category: &'a str,
+    serial: &'a HashMap<String, S>,
+) -> impl Iterator<Item = String> + 'a {
+    serial.keys().map(move |name| format!("{}:{}", category, name))
+}
+
+/// Creates the styles of a category that have not been created yet.
+/// Returns `true` if any new style was created.
+fn resolve<S, T>(
+    serial: &HashMap<String, S>,
+    theme: &mut Theme,
+    category: fn(&mut Theme) -> &mut HashMap<String, T>,
+    create: fn(&S, &Theme) -> Result<T, ()>,
+) -> bool {
+    let mut changed = false;
+
+    for (name, serial) in serial {
+        if category(theme).contains_key(name) {
+            continue;
+        }
 
-    fn appearance(&self, style: &Self::Style) -> iced::application::Appearance {
-        self.application.appearance(style)
+        if let Ok(style) = create(serial, theme) {
+            category(theme).insert(name.clone(), style);
+            changed = true;
+        }
+    }
+
+    changed
+}
+
+/// Fails with the first style of a category that could not be created.
+fn unresolved<S, T>(
+    category: &'static str,
+    serial: &HashMap<String, S>,
+    created: &HashMap<String, T>,
+) -> Result<(), Error> {
+    match serial.keys().find(|name| !created.contains_key(*name)) {
+        Some(name) => Err(Error::Unresolved {
+            category,
+            name: name.clone(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Copyable key to a named style of a `Theme`.
+/// Used by the `StyleSheet`s that require a `Copy` style, such as text.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct StyleId(pub &'static str);
+
+impl From<&'static str> for StyleId {
+    fn from(key: &'static str) -> Self {
+        StyleId(key)
     }
 }
 
 impl Into<iced::Theme> for Theme {
     fn into(self) -> iced::Theme {
-        iced::Theme::custom(iced::theme::Palette {
-            background: self.application.background_color.into(),
-            text: self.application.text_color.into(),
-            ..iced::theme::Palette::LIGHT
-        })
+        self.builtin()
     }
 }
 
@@ -85,22 +200,101 @@ impl Theme {
             color: HashMap::new(),
             container: HashMap::new(),
 
+            fallback: HashMap::new(),
+
+            palette: None,
             panegrid: HashMap::new(),
             picklist: HashMap::new(),
             progressbar: HashMap::new(),
 
             scrollable: HashMap::new(),
 
+            scopes: HashMap::new(),
+
+            text: HashMap::new(),
             textinput: HashMap::new(),
+            tokens: HashMap::new(),
             tooltip: HashMap::new(),
+
+            variant: None,
+            variants: Vec::new(),
+
+            warnings: Vec::new(),
+
+            context: Context::current(),
+
+            namespaces: HashMap::new(),
+            aliases: HashMap::new(),
+
+            pending: HashSet::new(),
+            renamed: HashSet::new(),
+        }
+    }
+
+    /// Creates the built-in `iced` theme that matches this theme.
+    /// Used as the fallback of the `StyleSheet`s when a style key is not found.
+    /// If the palette is not defined, the application colors are used over the light palette.
+    pub(crate) fn builtin(&self) -> iced::Theme {
+        match self.palette {
+            Some(palette) => iced::Theme::custom(palette.into()),
+            _ => iced::Theme::custom(iced::theme::Palette {
+                background: self.application.background_color.into(),
+                text: self.application.text_color.into(),
+                ..iced::theme::Palette::LIGHT
+            }),
         }
     }
 
-    /// Attempts to create a theme from its &serialized version.
-    pub fn parse(theme: &serial::Theme) -> Result<Self, ()> {
+    /// Gets the color with the given key.
+    pub(crate) fn color_key(&self, key: &str) -> Result<Color, ()> {
+        match self.color.get(key) {
+            Some(color) => Ok(*color),
+            _ => Color::parse(key),
+        }
+    }
+
+    /// Evaluates a number, which may reference the tokens.
+    pub(crate) fn number(&self, number: &crate::serial::Number) -> Result<f32, ()> {
+        token::evaluate(number, &self.tokens)
+    }
+
+    /// Gets the border with the given key.
+    pub(crate) fn border_key(&self, key: &str) -> Result<Border, ()> {
+        self.inherited("border", &self.border, key).copied().ok_or(())
+    }
+
+    /// Gets the border with the given key, or creates the inline border.
+    pub(crate) fn border_value(&self, value: &crate::serial::BorderValue) -> Result<Border, ()> {
+        match value {
+            crate::serial::BorderValue::Key(key) => self.border_key(key),
+            crate::serial::BorderValue::Literal(border) => Border::from(border, self),
+        }
+    }
+
+    /// Creates a theme from its &serialized version, evaluating the conditional overrides against
+    /// the given context. The conditional overrides take precedence over the base entries, and
+    /// the scopes over both.
+    fn create(theme: &serial::Theme, context: &Context) -> Result<Self, Error> {
+        // Apply the conditional overrides and cascade the dotted keys before creating any entry.
+        let conditioned = theme.conditioned(context);
+        let cascaded = conditioned.cascaded();
+
+        // Alias the renamed keys, as the styles and the application may use the new keys.
+        let mut warnings = Vec::new();
+        let aliased = cascaded.aliased(&mut warnings);
+        let theme = aliased.as_ref();
+
+        // Check the styles that inherit from other categories before creating them.
+        cross::validate(theme)?;
+        fallback::validate(theme)?;
+
         let mut new_theme = Self {
             name: theme.name.clone(),
             description: theme.description.clone(),
+            variants: theme.variants().into_iter().map(String::from).collect(),
+            context: context.clone(),
+            warnings,
+            renamed: theme.aliases.keys().cloned().collect(),
             ..Default::default()
         };
 
@@ -111,92 +305,201 @@ impl Theme {
             .map(|(n, c)| (n.clone(), c.clone()))
             .collect();
 
-        // De&serialize application
-        new_theme.application = Application::create(&theme.application, &new_theme)?;
+        // Resolve the tokens, as the numeric fields of all the styles depend on them.
+        new_theme.tokens =
+            token::resolve(&theme.tokens, HashMap::new()).map_err(|name| Error::Unresolved {
+                category: "token",
+                name,
+            })?;
 
-        // De&serialize the borders, as they only depend on colors.
-        for (name, serial) in &theme.border {
-            let v = Border::create(serial, &new_theme)?;
-            new_theme.border.insert(name.clone(), v);
+        // De&serialize the palette, as it only depends on colors.
+        if let Some(palette) = &theme.palette {
+            new_theme.palette = Some(Palette::create(palette, &new_theme)?);
         }
 
-        // De&serialize the progress bars, as they only depend on colors.
-        for (name, serial) in &theme.progressbar {
-            let v = ProgressBar::create(serial, &new_theme)?;
-            new_theme.progressbar.insert(name.clone(), v);
-        }
+        // De&serialize the styles, as they depend on the colors and tokens.
+        new_theme.create_styles(theme)?;
+
+        // De&serialize application, as its default styles depend on all the others.
+        new_theme.application = Application::create(&theme.application, &new_theme)?;
+
+        // Create the scopes, which override the entries of this theme.
+        // The scopes are aliased after their overrides, so they are created from the base theme.
+        new_theme.create_scopes(&cascaded, context)?;
+
+        Ok(new_theme)
+    }
 
-        // De&serialize the containers, as they only depend on colors and borders.
-        for (name, serial) in &theme.container {
-            let c = Container::create(serial, &new_theme)?;
-            new_theme.container.insert(name.clone(), c);
+    /// Creates the text styles, fallback rules and composable styles of a &serialized theme,
+    /// which may reference the colors, tokens and styles already in this theme.
+    fn create_styles(&mut self, theme: &serial::Theme) -> Result<(), Error> {
+        // De&serialize the text styles, as they only depend on colors.
+        for (name, key) in &theme.text {
+            let color = self.color_key(key).map_err(|_| Error::Unresolved {
+                category: "text",
+                name: name.clone(),
+            })?;
+            self.text.insert(name.clone(), color);
         }
 
-        // De&serialize the tooltips, as they only depend on colors and borders.
-        for (name, serial) in &theme.tooltip {
-            let c = Tooltip::create(serial, &new_theme)?;
-            new_theme.tooltip.insert(name.clone(), c);
+        // De&serialize the fallback rules, as the composable depend on them.
+        for (category, rules) in &theme.fallback {
+            let created = match category.as_str() {
+                "button" | "panegrid" | "picklist" | "scrollable" | "textinput" => {
+                    fallback::create(rules, category)
+                }
+
+                // Other categories have no states to fall back to.
+                _ => match rules.keys().min() {
+                    Some(state) => Err(Error::UnknownState {
+                        category: category.clone(),
+                        state: state.clone(),
+                    }),
+                    _ => Ok(HashMap::new()),
+                },
+            };
+
+            let rules = created.map_err(|error| Error::Entry {
+                category: "fallback",
+                name: category.clone(),
+                error: Box::new(error),
+            })?;
+
+            self.fallback.insert(category.clone(), rules);
         }
 
-        // De&serialize the composable.
-        // Allow a maximum depth of 10.
-        // TODO : Instead of maximum depth check for no changes in the set of buttons for a lock.
-        for _ in 0..10 {
-            // De&serialize the buttons.
-            for (name, serial) in &theme.button {
-                let b = Button::create(serial, &new_theme)?;
-                new_theme.button.insert(name.clone(), b);
-            }
+        // Mark the composable as pending, so their references wait until they are created.
+        self.pending = pending("border", &theme.border)
+            .chain(pending("button", &theme.button))
+            .chain(pending("container", &theme.container))
+            .chain(pending("panegrid", &theme.panegrid))
+            .chain(pending("picklist", &theme.picklist))
+            .chain(pending("progressbar", &theme.progressbar))
+            .chain(pending("scrollable", &theme.scrollable))
+            .chain(pending("textinput", &theme.textinput))
+            .chain(pending("tooltip", &theme.tooltip))
+            .collect();
 
-            // De&serialize the picklists.
-            for (name, serial) in &theme.panegrid {
-                let p = PaneGrid::create(serial, &new_theme)?;
-                new_theme.panegrid.insert(name.clone(), p);
+        // De&serialize the composable in dependency order.
+        // Each pass creates the styles whose inherited styles are already created,
+        // until a pass creates no new style.
+        loop {
+            let mut changed = false;
+            let t = &mut *self;
+
+            changed |= resolve(&theme.border, t, |t| &mut t.border, Border::create);
+            changed |= resolve(&theme.container, t, |t| &mut t.container, Container::create);
+            changed |= resolve(
+                &theme.progressbar,
+                t,
+                |t| &mut t.progressbar,
+                ProgressBar::create,
+            );
+            changed |= resolve(&theme.tooltip, t, |t| &mut t.tooltip, Tooltip::create);
+            changed |= resolve(&theme.button, t, |t| &mut t.button, Button::create);
+            changed |= resolve(&theme.panegrid, t, |t| &mut t.panegrid, PaneGrid::create);
+            changed |= resolve(&theme.picklist, t, |t| &mut t.picklist, Picklist::create);
+            changed |= resolve(&theme.scrollable, t, |t| &mut t.scrollable, Scrollable::create);
+            changed |= resolve(&theme.textinput, t, |t| &mut t.textinput, TextInput::create);
+
+            if !changed {
+                break;
             }
+        }
 
-            // De&serialize the picklists.
-            for (name, serial) in &theme.picklist {
-                let p = Picklist::create(serial, &new_theme)?;
-                new_theme.picklist.insert(name.clone(), p);
-            }
+        self.pending.clear();
 
-            // De&serialize the scrollables.
-            for (name, serial) in &theme.scrollable {
-                let s = Scrollable::create(serial, &new_theme)?;
-                new_theme.scrollable.insert(name.clone(), s);
-            }
+        // Fail if any style could not be created (missing keys or circular inheritance).
+        unresolved("border", &theme.border, &self.border)?;
+        unresolved("button", &theme.button, &self.button)?;
+        unresolved("container", &theme.container, &self.container)?;
+        unresolved("panegrid", &theme.panegrid, &self.panegrid)?;
+        unresolved("picklist", &theme.picklist, &self.picklist)?;
+        unresolved("progressbar", &theme.progressbar, &self.progressbar)?;
+        unresolved("scrollable", &theme.scrollable, &self.scrollable)?;
+        unresolved("textinput", &theme.textinput, &self.textinput)?;
+        unresolved("tooltip", &theme.tooltip, &self.tooltip)?;
 
-            // De&serialize the text inputs.
-            for (name, serial) in &theme.textinput {
-                let t = TextInput::create(serial, &new_theme)?;
-                new_theme.textinput.insert(name.clone(), t);
-            }
+        Ok(())
+    }
+
+    /// Creates the `iced` settings with the default text size of this theme.
+    /// The default font is only a reference, so it must be loaded by the application.
+    pub fn settings<Flags>(&self, flags: Flags) -> iced::Settings<Flags> {
+        let default = iced::Settings::with_flags(flags);
+
+        iced::Settings {
+            default_text_size: self
+                .application
+                .text_size
+                .unwrap_or(default.default_text_size),
+            ..default
         }
+    }
 
-        Ok(new_theme)
+    /// Gets the button style of a key, falling back to the less specific levels of the key.
+    /// An unknown key uses the built-in `iced` style.
+    pub fn get_button(&self, k: &str) -> iced::theme::Button {
+        cascade(&self.button, k).map_or_else(Default::default, |button| {
+            iced::theme::Button::Custom(Box::new(*button))
+        })
+    }
+
+    /// Gets the container style of a key, falling back to the less specific levels of the key.
+    /// An unknown key uses the built-in `iced` style.
+    pub fn get_container(&self, k: &str) -> iced::theme::Container {
+        cascade(&self.container, k).map_or_else(Default::default, |container| {
+            iced::theme::Container::Custom(Box::new(*container))
+        })
+    }
+
+    /// Gets the pane grid style of a key, falling back to the less specific levels of the key.
+    /// An unknown key uses the built-in `iced` style.
+    pub fn get_panegrid(&self, k: &str) -> iced::theme::PaneGrid {
+        cascade(&self.panegrid, k).map_or_else(Default::default, |panegrid| {
+            iced::theme::PaneGrid::Custom(Box::new(*panegrid))
+        })
     }
 
-    pub fn get_button(&self, k: &String) -> iced::theme::Button {
-        iced::theme::Button::Custom(Box::new(self.button.get(k).unwrap().clone()))
+    /// Gets the pick list style of a key, falling back to the less specific levels of the key.
+    /// An unknown key uses the built-in `iced` style.
+    pub fn get_picklist(&self, k: &str) -> iced::theme::PickList {
+        cascade(&self.picklist, k).map_or_else(Default::default, |picklist| (*picklist).into())
     }
 
-    pub fn get_container(&self, k: &String) -> iced::theme::Container {
-        iced::theme::Container::Custom(Box::new(self.container.get(k).unwrap().clone()))
+    /// Gets the progress bar style of a key, falling back to the less specific levels of the key.
+    /// An unknown key uses the built-in `iced` style.
+    pub fn get_progressbar(&self, k: &str) -> iced::theme::ProgressBar {
+        cascade(&self.progressbar, k).map_or_else(Default::default, |progressbar| {
+            iced::theme::ProgressBar::Custom(Box::new(*progressbar))
+        })
     }
 
-    pub fn get_panegrid(&self, k: &String) -> iced::theme::PaneGrid {
-        iced::theme::PaneGrid::Custom(Box::new(self.panegrid.get(k).unwrap().clone()))
+    /// Gets the scrollable style of a key, falling back to the less specific levels of the key.
+    /// An unknown key uses the built-in `iced` style.
+    pub fn get_scrollable(&self, k: &str) -> iced::theme::Scrollable {
+        cascade(&self.scrollable, k).map_or_else(Default::default, |scrollable| {
+            iced::theme::Scrollable::Custom(Box::new(*scrollable))
+        })
     }
 
-    pub fn get_progressbar(&self, k: &String) -> iced::theme::ProgressBar {
-        iced::theme::ProgressBar::Custom(Box::new(self.progressbar.get(k).unwrap().clone()))
+    /// Gets the text style of a key, falling back to the less specific levels of the key.
+    /// An unknown key uses the built-in `iced` style.
+    pub fn get_text(&self, k: &str) -> iced::theme::Text {
+        cascade(&self.text, k).map_or_else(Default::default, |text| text.into())
     }
 
-    pub fn get_scrollable(&self, k: &String) -> iced::theme::Scrollable {
-        iced::theme::Scrollable::Custom(Box::new(self.scrollable.get(k).unwrap().clone()))
+    /// Gets the tooltip style of a key, falling back to the less specific levels of the key.
+    /// An unknown key uses the built-in `iced` style.
+    pub fn get_tooltip(&self, k: &str) -> iced::theme::Container {
+        cascade(&self.tooltip, k).map_or_else(Default::default, |tooltip| (*tooltip).into())
     }
 
-    pub fn get_textinput(&self, k: &String) -> iced::theme::TextInput{
-        iced::theme::TextInput::Custom(Box::new(self.textinput.get(k).unwrap().clone()))
+    /// Gets the text input style of a key, falling back to the less specific levels of the key.
+    /// An unknown key uses the built-in `iced` style.
+    pub fn get_textinput(&self, k: &str) -> iced::theme::TextInput {
+        cascade(&self.textinput, k).map_or_else(Default::default, |textinput| {
+            iced::theme::TextInput::Custom(Box::new(*textinput))
+        })
     }
 }
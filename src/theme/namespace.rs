@@ -0,0 +1,278 @@
+//! Namespaced styles registered by plugins.
+//!
+//! A namespace is a fragment registered into a parsed theme. Its entries are addressed with the
+//! name of the namespace (e.g. `"plugin-x::primary"`), so they do not collide with the entries
+//! of the theme or of other namespaces. The references of the fragment are resolved first
+//! against its own entries, with or without the name of the namespace, and then against the
+//! entries of the theme.
+
+use super::{cross, serial, Fragment, Theme};
+use crate::{token, Error};
+
+use std::collections::HashMap;
+
+/// Separator between the name of a namespace and the key of an entry.
+const SEPARATOR: &str = "::";
+
+/// Removes the entries of the theme that are shadowed by the entries of the fragment.
+fn shadow<S, T>(target: &mut HashMap<String, T>, source: &HashMap<String, S>) {
+    target.retain(|name, _| !source.contains_key(name));
+}
+
+/// Copies the entries created from the fragment into the theme, under the namespace.
+fn export<S, T: Clone>(
+    namespace: &str,
+    target: &mut HashMap<String, T>,
+    created: &HashMap<String, T>,
+    source: &HashMap<String, S>,
+) {
+    for name in source.keys() {
+        if let Some(entry) = created.get(name) {
+            target.insert(format!("{}{}{}", namespace, SEPARATOR, name), entry.clone());
+        }
+    }
+}
+
+/// Adds a copy of each entry of the fragment under its qualified name, so the entries of the
+/// fragment can reference each other with the name of the namespace.
+fn qualify<T: Clone>(namespace: &str, source: &mut HashMap<String, T>) {
+    let qualified: Vec<_> = source
+        .iter()
+        .map(|(name, entry)| (format!("{}{}{}", namespace, SEPARATOR, name), entry.clone()))
+        .collect();
+
+    source.extend(qualified);
+}
+
+/// Removes the entries of a namespace from the theme.
+/// Returns `true` if any entry was removed.
+fn remove<T>(prefix: &str, target: &mut HashMap<String, T>) -> bool {
+    let len = target.len();
+    target.retain(|name, _| !name.starts_with(prefix));
+    target.len() != len
+}
+
+impl Theme {
+    /// Registers the entries of a fragment under a namespace, in this theme and in its scopes,
+    /// replacing the entries previously registered under it.
+    /// The fragments included by the fragment are not loaded.
+    /// The theme is not modified if any entry of the fragment cannot be created.
+    pub fn register_namespace(&mut self, namespace: &str, fragment: Fragment) -> Result<(), Error> {
+        let wrap = |error| Error::Namespace {
+            namespace: namespace.into(),
+            error: Box::new(error),
+        };
+
+        let registered = fragment.clone();
+
+        // Register the entries in the scopes, which resolve them against their own entries.
+        let mut scopes = self.scopes.clone();
+
+        for (name, scope) in &mut scopes {
+            scope
+                .register_namespace(namespace, fragment.clone())
+                .map_err(|error| Error::Scope {
+                    scope: name.clone(),
+                    error: Box::new(error),
+                })?;
+        }
+
+        let fragment = serial::Theme {
+            name: namespace.into(),
+            description: String::new(),
+            application: crate::serial::Application {
+                background_color: String::new(),
+                text_color: String::new(),
+                text_size: None,
+                font: None,
+                defaults: Default::default(),
+            },
+            aliases: HashMap::new(),
+            include: Vec::new(),
+            border: fragment.border,
+            button: fragment.button,
+            color: fragment.color,
+            container: fragment.container,
+            fallback: fragment.fallback,
+            palette: None,
+            panegrid: fragment.panegrid,
+            picklist: fragment.picklist,
+            progressbar: fragment.progressbar,
+            scrollable: fragment.scrollable,
+            scopes: HashMap::new(),
+            text: fragment.text,
+            textinput: fragment.textinput,
+            tokens: fragment.tokens,
+            tooltip: fragment.tooltip,
+            variants: HashMap::new(),
+            conditions: Vec::new(),
+        };
+
+        // Inherit the undefined states of the dotted keys from the entries of the fragment.
+        let fragment = fragment.cascaded().into_owned();
+
+        cross::validate(&fragment).map_err(wrap)?;
+        crate::fallback::validate(&fragment).map_err(wrap)?;
+
+        // Create the entries in a copy of the theme, where they shadow the entries of the theme
+        // and the entries previously registered under the namespace.
+        let mut local = Theme {
+            scopes: HashMap::new(),
+            ..self.clone()
+        };
+
+        local.unregister_namespace(namespace);
+
+        let mut qualified = fragment.clone();
+        qualify(namespace, &mut qualified.border);
+        qualify(namespace, &mut qualified.button);
+        qualify(namespace, &mut qualified.color);
+        qualify(namespace, &mut qualified.container);
+        qualify(namespace, &mut qualified.panegrid);
+        qualify(namespace, &mut qualified.picklist);
+        qualify(namespace, &mut qualified.progressbar);
+        qualify(namespace, &mut qualified.scrollable);
+        qualify(namespace, &mut qualified.text);
+        qualify(namespace, &mut qualified.textinput);
+        qualify(namespace, &mut qualified.tokens);
+        qualify(namespace, &mut qualified.tooltip);
+
+        shadow(&mut local.border, &fragment.border);
+        shadow(&mut local.button, &fragment.button);
+        shadow(&mut local.container, &fragment.container);
+        shadow(&mut local.panegrid, &fragment.panegrid);
+        shadow(&mut local.picklist, &fragment.picklist);
+        shadow(&mut local.progressbar, &fragment.progressbar);
+        shadow(&mut local.scrollable, &fragment.scrollable);
+        shadow(&mut local.text, &fragment.text);
+        shadow(&mut local.textinput, &fragment.textinput);
+        shadow(&mut local.tokens, &fragment.tokens);
+        shadow(&mut local.tooltip, &fragment.tooltip);
+
+        local.color.extend(qualified.color.clone());
+
+        local.tokens = token::resolve(&qualified.tokens, local.tokens).map_err(|name| {
+            wrap(Error::Unresolved {
+                category: "token",
+                name,
+            })
+        })?;
+
+        local.create_styles(&qualified).map_err(wrap)?;
+
+        // Replace the entries previously registered under the namespace.
+        self.unregister_namespace(namespace);
+
+        let (n, f, s) = (namespace, &fragment, &local);
+        export(n, &mut self.border, &s.border, &f.border);
+        export(n, &mut self.button, &s.button, &f.button);
+        export(n, &mut self.color, &s.color, &f.color);
+        export(n, &mut self.container, &s.container, &f.container);
+        export(n, &mut self.panegrid, &s.panegrid, &f.panegrid);
+        export(n, &mut self.picklist, &s.picklist, &f.picklist);
+        export(n, &mut self.progressbar, &s.progressbar, &f.progressbar);
+        export(n, &mut self.scrollable, &s.scrollable, &f.scrollable);
+        export(n, &mut self.text, &s.text, &f.text);
+        export(n, &mut self.textinput, &s.textinput, &f.textinput);
+        export(n, &mut self.tokens, &s.tokens, &f.tokens);
+        export(n, &mut self.tooltip, &s.tooltip, &f.tooltip);
+
+        self.scopes = scopes;
+        self.namespaces.insert(namespace.into(), registered);
+
+        Ok(())
+    }
+
+    /// Removes the entries registered under a namespace (e.g. when a plugin is unloaded).
+    /// Returns `true` if any entry was removed.
+    pub fn unregister_namespace(&mut self, namespace: &str) -> bool {
+        let prefix = format!("{}{}", namespace, SEPARATOR);
+        let mut removed = self.namespaces.remove(namespace).is_some();
+
+        removed |= remove(&prefix, &mut self.border);
+        removed |= remove(&prefix, &mut self.button);
+        removed |= remove(&prefix, &mut self.color);
+        removed |= remove(&prefix, &mut self.container);
+        removed |= remove(&prefix, &mut self.panegrid);
+        removed |= remove(&prefix, &mut self.picklist);
+        removed |= remove(&prefix, &mut self.progressbar);
+        removed |= remove(&prefix, &mut self.scrollable);
+        removed |= remove(&prefix, &mut self.text);
+        removed |= remove(&prefix, &mut self.textinput);
+        removed |= remove(&prefix, &mut self.tokens);
+        removed |= remove(&prefix, &mut self.tooltip);
+
+        for scope in self.scopes.values_mut() {
+            scope.unregister_namespace(namespace);
+        }
+
+        removed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Color, Options};
+
+    const RED: Color = Color(255, 0, 0, 1.0);
+    const WHITE: Color = Color(255, 255, 255, 1.0);
+
+    fn theme() -> serial::Theme {
+        ron::from_str(
+            r##"(
+                name: "test",
+                description: "",
+                application: (background_color: "white", text_color: "black"),
+                color: { "white": "#ffffff", "black": "#000000" },
+                variants: { "dark": (color: { "white": "#ff0000" }) },
+            )"##,
+        )
+        .unwrap()
+    }
+
+    fn fragment() -> Fragment {
+        ron::from_str(
+            r##"(
+                border: { "thin": (color: "black", radius: 1.0, width: 1.0) },
+                button: {
+                    "a": (active: (background: "white", text: "black", border: "px::thin")),
+                    "b": (active: "px::a"),
+                },
+            )"##,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn qualified_references() {
+        let mut theme = Theme::parse(&theme()).unwrap();
+        theme.register_namespace("px", fragment()).unwrap();
+
+        assert_eq!(theme.button["px::b"].state[0].background, WHITE);
+    }
+
+    #[test]
+    fn switch() {
+        let serial = theme();
+        let mut theme = Theme::parse(&serial).unwrap();
+        theme.register_namespace("px", fragment()).unwrap();
+        theme.alias(&HashMap::from([("px::b".into(), "px::c".into())]));
+
+        theme.switch(&serial, &Options::new().variant("dark")).unwrap();
+
+        assert_eq!(theme.button["px::b"].state[0].background, RED);
+        assert_eq!(theme.button["px::c"].state[0].background, RED);
+        assert_eq!(theme.warnings.len(), 1);
+    }
+
+    #[test]
+    fn unregister() {
+        let mut theme = Theme::parse(&theme()).unwrap();
+        theme.register_namespace("px", fragment()).unwrap();
+
+        assert!(theme.unregister_namespace("px"));
+        assert!(theme.namespaces.is_empty());
+        assert!(!theme.button.contains_key("px::a"));
+    }
+}
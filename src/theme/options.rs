@@ -0,0 +1,76 @@
+//! Options of the creation of a theme from its &serialized version.
+//!
+//! The options select the context the conditional overrides are evaluated against, the variant
+//! that is created and the resolver that loads the included fragments. The entries are applied
+//! in order of precedence: the included fragments, the base theme, the variant, the conditional
+//! overrides and the scopes.
+
+use super::{serial, Context, Resolver, Theme};
+use crate::Error;
+
+/// Options of `Theme::parse_with` and `Theme::switch`.
+#[derive(Clone, Default)]
+pub struct Options<'a> {
+    /// Context the conditional overrides are evaluated against.
+    /// `None` is the current platform, or the context of the theme when switching variants.
+    pub context: Option<Context>,
+
+    /// Variant that is created, or `None` for the base theme.
+    pub variant: Option<String>,
+
+    /// Resolver that loads the included fragments.
+    pub resolver: Option<&'a dyn Resolver>,
+}
+
+impl<'a> Options<'a> {
+    /// Creates the default options: the base theme in the current context, without includes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Evaluates the conditional overrides against the given context.
+    pub fn context(mut self, context: Context) -> Self {
+        self.context = Some(context);
+        self
+    }
+
+    /// Creates the given variant.
+    pub fn variant(mut self, variant: &str) -> Self {
+        self.variant = Some(variant.into());
+        self
+    }
+
+    /// Loads the included fragments with the given resolver.
+    pub fn resolver(mut self, resolver: &'a dyn Resolver) -> Self {
+        self.resolver = Some(resolver);
+        self
+    }
+}
+
+impl Theme {
+    /// Attempts to create a theme from its &serialized version, with the default options.
+    pub fn parse(theme: &serial::Theme) -> Result<Self, Error> {
+        Self::parse_with(theme, &Options::new())
+    }
+
+    /// Attempts to create a theme from its &serialized version with the given options.
+    /// The errors of the entries defined in an included fragment report the path of the fragment.
+    /// A theme that includes fragments fails if no resolver is given.
+    pub fn parse_with(theme: &serial::Theme, options: &Options) -> Result<Self, Error> {
+        let context = options.context.clone().unwrap_or_default();
+        let variant = options.variant.as_deref();
+
+        match (options.resolver, theme.include.first()) {
+            (Some(resolver), _) => theme.create_merged(resolver, |merged| {
+                Self::create_variant(merged, variant, &context)
+            }),
+
+            (None, Some(file)) => Err(Error::Include {
+                file: file.clone(),
+                reason: "no resolver was given".into(),
+            }),
+
+            (None, None) => Self::create_variant(theme, variant, &context),
+        }
+    }
+}
@@ -0,0 +1,53 @@
+//! Scoped sub-themes for regions of the UI (e.g. a dark sidebar inside a light window).
+//!
+//! A scope is a fragment that overrides the entries of the theme in a region. The scoped
+//! theme is created again from the overridden entries, so the styles that depend on an
+//! overridden color are created with the new color, and the other entries are shared.
+
+use super::{serial, Context, Theme};
+use crate::Error;
+
+impl serial::Theme {
+    /// Creates the &serialized theme of a scope, overriding the entries of this theme with the
+    /// entries of the scope. The scoped theme does not declare any scopes.
+    pub fn scope(&self, name: &str) -> Option<Self> {
+        let scope = self.scopes.get(name)?;
+
+        let mut theme = self.clone();
+        theme.scopes.clear();
+        theme.apply(scope.clone());
+
+        Some(theme)
+    }
+}
+
+impl Theme {
+    /// Creates the scopes declared by a &serialized theme in the given context.
+    pub(crate) fn create_scopes(
+        &mut self,
+        theme: &serial::Theme,
+        context: &Context,
+    ) -> Result<(), Error> {
+        let scopes = theme
+            .scopes
+            .keys()
+            .filter_map(|name| Some((name, theme.scope(name)?)));
+
+        for (name, scoped) in scopes {
+            let scoped = Self::create(&scoped, context).map_err(|error| Error::Scope {
+                scope: name.clone(),
+                error: Box::new(error),
+            })?;
+
+            self.scopes.insert(name.clone(), scoped);
+        }
+
+        Ok(())
+    }
+
+    /// Gets the theme of a scope.
+    /// Scopes that are not declared use this theme, so every scope falls back to its parent.
+    pub fn scoped(&self, scope: &str) -> &Self {
+        self.scopes.get(scope).unwrap_or(self)
+    }
+}
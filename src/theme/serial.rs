@@ -1,6 +1,6 @@
 //! Serial version of the theme.
 
-use super::Theme as NormalTheme;
+use super::{Conditional, Fragment, Theme as NormalTheme};
 use crate::{serial::*, Color};
 
 use serde::{Deserialize, Serialize};
@@ -20,160 +20,246 @@ pub struct Theme {
     /// General Application Theme
     pub application: Application,
 
+    /// Maps old keys to their new keys, in every category (including colors and tokens).
+    /// The entries defined with an old key are reported as deprecated.
+    #[serde(default)]
+    pub aliases: HashMap<String, String>,
+
+    /// Paths of the fragments included by this theme.
+    /// See `Options::resolver` to merge them.
+    #[serde(default)]
+    pub include: Vec<String>,
+
     /// Maps name keys to border themes.
-    pub border: HashMap<String, Border>,
+    #[serde(default)]
+    pub border: HashMap<String, BorderComponent>,
 
     // Maps name keys to button themes.
-    pub button: HashMap<String, Button>,
+    #[serde(default)]
+    pub button: HashMap<String, ButtonStyle>,
 
     /// Maps name keys to colors.
+    #[serde(default)]
     pub color: HashMap<String, Color>,
 
     /// Maps name keys to containers.
-    pub container: HashMap<String, Container>,
+    #[serde(default)]
+    pub container: HashMap<String, ContainerComponent>,
+
+    /// Maps category names to the fallback rules of their states.
+    #[serde(default)]
+    pub fallback: HashMap<String, HashMap<String, Fallback>>,
+
+    /// Palette of the built-in `iced` styles.
+    #[serde(default)]
+    pub palette: Option<Palette>,
 
     /// Maps name keys to pane grids.
+    #[serde(default)]
     pub panegrid: HashMap<String, PaneGrid>,
 
     /// Maps name keys to picklists.
-    pub picklist: HashMap<String, Picklist>,
+    #[serde(default)]
+    pub picklist: HashMap<String, PicklistStyle>,
 
     /// Maps name keys to progress bar.
-    pub progressbar: HashMap<String, ProgressBar>,
+    #[serde(default)]
+    pub progressbar: HashMap<String, ProgressBarComponent>,
 
     /// Maps name keys to scrollable.
+    #[serde(default)]
     pub scrollable: HashMap<String, Scrollable>,
 
+    /// Maps scope names to fragments that override the entries of this theme in a region of the UI.
+    /// The fragments included by a scope are not loaded.
+    #[serde(default)]
+    pub scopes: HashMap<String, Fragment>,
+
+    /// Maps name keys to text color keys.
+    #[serde(default)]
+    pub text: HashMap<String, String>,
+
     /// Maps name keys to text input.
-    pub textinput: HashMap<String, TextInput>,
+    #[serde(default)]
+    pub textinput: HashMap<String, TextInputStyle>,
+
+    /// Maps name keys to numeric tokens.
+    #[serde(default)]
+    pub tokens: HashMap<String, Number>,
 
     /// Maps name keys to tooltip.
-    pub tooltip: HashMap<String, Tooltip>,
+    #[serde(default)]
+    pub tooltip: HashMap<String, TooltipComponent>,
+
+    /// Maps name keys to variants, which override the entries of this theme.
+    /// The fragments included by a variant are not loaded.
+    #[serde(default)]
+    pub variants: HashMap<String, Fragment>,
+
+    /// Overrides of the entries of this theme, applied when their condition matches the context
+    /// the theme is created in (e.g. the platform).
+    #[serde(default)]
+    pub conditions: Vec<Conditional>,
 }
 
-fn get_color_name(colors: &mut HashMap<String, Color>, c: &Color) -> String {
-    let value = colors.iter().find(|color| color.1 == c).map(|r| r.0.to_string());
-    if value.is_none() {
-        let default_name = format!("color_{}", colors.len());
-        colors.insert(default_name.clone(), c.clone());
-        return default_name;
-    };
-    value.unwrap()
+/// Gets the first (sorted) key of a color of the theme, or the color literal if it is not named.
+fn get_color_name(colors: &HashMap<String, Color>, c: &Color) -> String {
+    let name = colors
+        .iter()
+        .filter(|(_, color)| *color == c)
+        .map(|(name, _)| name)
+        .min();
+
+    match name {
+        Some(name) => name.clone(),
+        _ => c.literal(),
+    }
 }
 
+/// Gets the first (sorted) key of a border of the theme, or the inline border if it is not named.
 fn get_border_name(
-    colors: &mut HashMap<String, Color>,
-    borders: &mut HashMap<String, Border>,
+    colors: &HashMap<String, Color>,
+    borders: &HashMap<String, BorderComponent>,
     b: &crate::Border,
-) -> String {
-    let color_name = get_color_name(colors, &b.color);
-    let value = borders
+) -> BorderValue {
+    let border = Border {
+        color: get_color_name(colors, &b.color),
+        radius: b.radius.into(),
+        width: b.width.into(),
+    };
+
+    let name = borders
         .iter()
-        .find(|border| {
-            border.1.radius == b.radius
-                && border.1.color == color_name.clone()
-                && border.1.width == b.width
+        .filter(|(_, component)| match component {
+            BorderComponent::Defined(defined) => *defined == border,
+            _ => false,
         })
-        .map(|r| r.0.to_string());
-    if value.is_none() {
-        let default_name = format!("border_{}", borders.len());
-        borders.insert(
-            default_name.clone(),
-            Border {
-                color: color_name.clone(),
-                radius: b.radius,
-                width: b.width,
-            },
-        );
-        return default_name;
-    };
-    value.unwrap()
+        .map(|(name, _)| name)
+        .min();
+
+    match name {
+        Some(name) => BorderValue::Key(name.clone()),
+        _ => BorderValue::Literal(Box::new(border)),
+    }
+}
+
+fn get_scrollable_state(
+    colors: &HashMap<String, Color>,
+    borders: &HashMap<String, BorderComponent>,
+    s: &crate::ScrollableState,
+) -> ScrollableState {
+    ScrollableState {
+        color: get_color_name(colors, &s.color),
+        border: get_border_name(colors, borders, &s.border),
+        scolor: get_color_name(colors, &s.scolor),
+        sborder: get_border_name(colors, borders, &s.sborder),
+    }
 }
 
 impl From<NormalTheme> for Theme {
     fn from(theme: NormalTheme) -> Self {
+        // The entries of the namespaces and the entries copied by the aliases are not serialized.
+        let own = |name: &str| !name.contains("::") && !theme.renamed.contains(name);
+
         // Deserialize all the colors.
-        let mut color = theme
+        let color = theme
             .color
             .iter()
+            .filter(|(name, _)| own(name))
             .map(|(n, c)| (n.to_string(), c.clone()))
             .collect::<HashMap<String, Color>>();
-        let mut border = theme
+        let border = theme
             .border
             .iter()
+            .filter(|(name, _)| own(name))
             .map(|(n, b)| {
                 (
                     n.to_string(),
-                    Border {
-                        color: get_color_name(&mut color, &b.color),
-                        radius: b.radius,
-                        width: b.width,
-                    },
+                    BorderComponent::Defined(Border {
+                        color: get_color_name(&color, &b.color),
+                        radius: b.radius.into(),
+                        width: b.width.into(),
+                    }),
                 )
             })
-            .collect::<HashMap<String, Border>>();
+            .collect::<HashMap<String, BorderComponent>>();
 
         let button = theme
             .button
             .iter()
+            .filter(|(name, _)| own(name))
             .map(|(name, btn)| {
                 (
                     name.to_string(),
                     Button {
                         active: ButtonComponent::Defined(ButtonState {
-                            background: get_color_name(&mut color, &btn.state[0].background),
-                            text: get_color_name(&mut color, &btn.state[0].text),
-                            border: get_border_name(&mut color, &mut border, &btn.state[0].border),
+                            background: get_color_name(&color, &btn.state[0].background),
+                            text: get_color_name(&color, &btn.state[0].text),
+                            border: get_border_name(&color, &border, &btn.state[0].border),
                         }),
                         hovered: ButtonComponent::Defined(ButtonState {
-                            background: get_color_name(&mut color, &btn.state[1].background),
-                            text: get_color_name(&mut color, &btn.state[1].text),
-                            border: get_border_name(&mut color, &mut border, &btn.state[1].border),
+                            background: get_color_name(&color, &btn.state[1].background),
+                            text: get_color_name(&color, &btn.state[1].text),
+                            border: get_border_name(&color, &border, &btn.state[1].border),
                         }),
                         pressed: ButtonComponent::Defined(ButtonState {
-                            background: get_color_name(&mut color, &btn.state[2].background),
-                            text: get_color_name(&mut color, &btn.state[2].text),
-                            border: get_border_name(&mut color, &mut border, &btn.state[2].border),
+                            background: get_color_name(&color, &btn.state[2].background),
+                            text: get_color_name(&color, &btn.state[2].text),
+                            border: get_border_name(&color, &border, &btn.state[2].border),
                         }),
                         disabled: ButtonComponent::Defined(ButtonState {
-                            background: get_color_name(&mut color, &btn.state[3].background),
-                            text: get_color_name(&mut color, &btn.state[3].text),
-                            border: get_border_name(&mut color, &mut border, &btn.state[3].border),
+                            background: get_color_name(&color, &btn.state[3].background),
+                            text: get_color_name(&color, &btn.state[3].text),
+                            border: get_border_name(&color, &border, &btn.state[3].border),
                         }),
+                        fallback: HashMap::new(),
                     },
                 )
             })
-            .collect::<HashMap<String, Button>>();
+            .map(|(name, button)| (name, ButtonStyle::States(Box::new(button))))
+            .collect::<HashMap<String, ButtonStyle>>();
 
         let container = theme
             .container
             .iter()
+            .filter(|(name, _)| own(name))
             .map(|(name, c)| {
                 (
                     name.to_string(),
-                    Container {
-                        color: get_color_name(&mut color, &c.color),
-                        border: get_border_name(&mut color, &mut border, &c.border),
-                    },
+                    ContainerComponent::Defined(Container {
+                        color: get_color_name(&color, &c.color),
+                        border: get_border_name(&color, &border, &c.border),
+                        text: c.text.map(|t| get_color_name(&color, &t)),
+                    }),
                 )
             })
-            .collect::<HashMap<String, Container>>();
+            .collect::<HashMap<String, ContainerComponent>>();
+
+        let palette = theme.palette.map(|p| Palette {
+            background: get_color_name(&color, &p.background),
+            text: get_color_name(&color, &p.text),
+            primary: get_color_name(&color, &p.primary),
+            success: get_color_name(&color, &p.success),
+            danger: get_color_name(&color, &p.danger),
+        });
 
         let panegrid = theme
             .panegrid
             .iter()
+            .filter(|(name, _)| own(name))
             .map(|(name, p)| {
                 (
                     name.to_string(),
                     PaneGrid {
                         picked: PaneGridComponent::Defined(PaneGridState {
-                            color: get_color_name(&mut color, &p.state[0].color),
-                            width: p.state[0].width,
+                            color: get_color_name(&color, &p.state[0].color),
+                            width: p.state[0].width.into(),
                         }),
                         hovered: PaneGridComponent::Defined(PaneGridState {
-                            color: get_color_name(&mut color, &p.state[1].color),
-                            width: p.state[1].width,
+                            color: get_color_name(&color, &p.state[1].color),
+                            width: p.state[1].width.into(),
                         }),
+                        fallback: HashMap::new(),
                     },
                 )
             })
@@ -182,76 +268,96 @@ impl From<NormalTheme> for Theme {
         let picklist = theme
             .picklist
             .iter()
+            .filter(|(name, _)| own(name))
             .map(|(name, p)| {
                 (
                     name.to_string(),
                     Picklist {
                         active: PicklistStateComponent::Defined(PicklistState {
-                            background: get_color_name(&mut color, &p.state[0].background),
-                            text: get_color_name(&mut color, &p.state[0].text),
-                            placeholder: get_color_name(&mut color, &p.state[0].placeholder),
-                            border: get_border_name(&mut color, &mut border, &p.state[0].border),
-                            handle: get_color_name(&mut color, &p.state[0].handle),
+                            background: get_color_name(&color, &p.state[0].background),
+                            text: get_color_name(&color, &p.state[0].text),
+                            placeholder: get_color_name(&color, &p.state[0].placeholder),
+                            border: get_border_name(&color, &border, &p.state[0].border),
+                            handle: get_color_name(&color, &p.state[0].handle),
                         }),
                         hovered: PicklistStateComponent::Defined(PicklistState {
-                            background: get_color_name(&mut color, &p.state[1].background),
-                            text: get_color_name(&mut color, &p.state[1].text),
-                            placeholder: get_color_name(&mut color, &p.state[1].placeholder),
-                            border: get_border_name(&mut color, &mut border, &p.state[1].border),
-                            handle: get_color_name(&mut color, &p.state[1].handle),
+                            background: get_color_name(&color, &p.state[1].background),
+                            text: get_color_name(&color, &p.state[1].text),
+                            placeholder: get_color_name(&color, &p.state[1].placeholder),
+                            border: get_border_name(&color, &border, &p.state[1].border),
+                            handle: get_color_name(&color, &p.state[1].handle),
                         }),
                         menu: PicklistMenuComponent::Defined(PicklistMenu {
-                            background: get_color_name(&mut color, &p.menu.background[0]),
-                            text: get_color_name(&mut color, &p.menu.text[0]),
-                            border: get_border_name(&mut color, &mut border, &p.menu.border),
-                            sbackground: get_color_name(&mut color, &p.menu.background[1]),
-                            stext: get_color_name(&mut color, &p.menu.text[1]),
+                            background: get_color_name(&color, &p.menu.background[0]),
+                            text: get_color_name(&color, &p.menu.text[0]),
+                            border: get_border_name(&color, &border, &p.menu.border),
+                            sbackground: get_color_name(&color, &p.menu.background[1]),
+                            stext: get_color_name(&color, &p.menu.text[1]),
                         }),
+                        fallback: HashMap::new(),
                     },
                 )
             })
-            .collect::<HashMap<String, Picklist>>();
+            .map(|(name, picklist)| (name, PicklistStyle::States(picklist)))
+            .collect::<HashMap<String, PicklistStyle>>();
 
         let progressbar = theme
             .progressbar
             .iter()
+            .filter(|(name, _)| own(name))
             .map(|(name, p)| {
                 (
                     name.to_string(),
-                    ProgressBar {
-                        background: get_color_name(&mut color, &p.background),
-                        bar: get_color_name(&mut color, &p.bar),
-                        radius: p.radius,
-                    },
+                    ProgressBarComponent::Defined(ProgressBar {
+                        background: get_color_name(&color, &p.background),
+                        bar: get_color_name(&color, &p.bar),
+                        radius: p.radius.into(),
+                    }),
                 )
             })
-            .collect::<HashMap<String, ProgressBar>>();
+            .collect::<HashMap<String, ProgressBarComponent>>();
 
         let scrollable = theme
             .scrollable
             .iter()
+            .filter(|(name, _)| own(name))
             .map(|(name, s)| {
                 (
                     name.to_string(),
                     Scrollable {
-                        active: ScrollableComponent::Defined(ScrollableState {
-                            color: get_color_name(&mut color, &s.state[0].color),
-                            border: get_border_name(&mut color, &mut border, &s.state[0].border),
-                            scolor: get_color_name(&mut color, &s.state[0].scolor),
-                            sborder: get_border_name(&mut color, &mut border, &s.state[0].sborder),
-                        }),
-                        hovered: ScrollableComponent::Defined(ScrollableState {
-                            color: get_color_name(&mut color, &s.state[1].color),
-                            border: get_border_name(&mut color, &mut border, &s.state[1].border),
-                            scolor: get_color_name(&mut color, &s.state[1].scolor),
-                            sborder: get_border_name(&mut color, &mut border, &s.state[1].sborder),
-                        }),
-                        dragging: ScrollableComponent::Defined(ScrollableState {
-                            color: get_color_name(&mut color, &s.state[2].color),
-                            border: get_border_name(&mut color, &mut border, &s.state[2].border),
-                            scolor: get_color_name(&mut color, &s.state[2].scolor),
-                            sborder: get_border_name(&mut color, &mut border, &s.state[2].sborder),
+                        active: ScrollableComponent::Defined(get_scrollable_state(
+                            &color,
+                            &border,
+                            &s.state[0],
+                        )),
+                        hovered: ScrollableComponent::Defined(get_scrollable_state(
+                            &color,
+                            &border,
+                            &s.state[1],
+                        )),
+                        dragging: ScrollableComponent::Defined(get_scrollable_state(
+                            &color,
+                            &border,
+                            &s.state[2],
+                        )),
+                        horizontal: Some(ScrollableAxis {
+                            active: ScrollableComponent::Defined(get_scrollable_state(
+                                &color,
+                                &border,
+                                &s.horizontal[0],
+                            )),
+                            hovered: ScrollableComponent::Defined(get_scrollable_state(
+                                &color,
+                                &border,
+                                &s.horizontal[1],
+                            )),
+                            dragging: ScrollableComponent::Defined(get_scrollable_state(
+                                &color,
+                                &border,
+                                &s.horizontal[2],
+                            )),
                         }),
+                        fallback: HashMap::new(),
                     },
                 )
             })
@@ -260,62 +366,204 @@ impl From<NormalTheme> for Theme {
         let textinput = theme
             .textinput
             .iter()
+            .filter(|(name, _)| own(name))
             .map(|(name, t)| {
                 (
                     name.to_string(),
                     TextInput {
                         active: TextInputComponent::Defined(TextInputState {
-                            background: get_color_name(&mut color, &t.state[0].background),
-                            border: get_border_name(&mut color, &mut border, &t.state[0].border),
+                            background: get_color_name(&color, &t.state[0].background),
+                            border: get_border_name(&color, &border, &t.state[0].border),
                         }),
                         hovered: TextInputComponent::Defined(TextInputState {
-                            background: get_color_name(&mut color, &t.state[1].background),
-                            border: get_border_name(&mut color, &mut border, &t.state[1].border),
+                            background: get_color_name(&color, &t.state[1].background),
+                            border: get_border_name(&color, &border, &t.state[1].border),
                         }),
                         focused: TextInputComponent::Defined(TextInputState {
-                            background: get_color_name(&mut color, &t.state[2].background),
-                            border: get_border_name(&mut color, &mut border, &t.state[2].border),
+                            background: get_color_name(&color, &t.state[2].background),
+                            border: get_border_name(&color, &border, &t.state[2].border),
                         }),
-                        placeholder: get_color_name(&mut color, &t.colors[0]),
-                        value: get_color_name(&mut color, &t.colors[1]),
-                        selection: get_color_name(&mut color, &t.colors[2]),
+                        placeholder: get_color_name(&color, &t.colors[0]),
+                        value: get_color_name(&color, &t.colors[1]),
+                        selection: get_color_name(&color, &t.colors[2]),
+                        fallback: HashMap::new(),
                     },
                 )
             })
-            .collect::<HashMap<String, TextInput>>();
+            .map(|(name, textinput)| (name, TextInputStyle::States(textinput)))
+            .collect::<HashMap<String, TextInputStyle>>();
+
+        let text = theme
+            .text
+            .iter()
+            .filter(|(name, _)| own(name))
+            .map(|(name, c)| (name.to_string(), get_color_name(&color, c)))
+            .collect::<HashMap<String, String>>();
 
         let tooltip = theme
             .tooltip
             .iter()
+            .filter(|(name, _)| own(name))
             .map(|(name, t)| {
                 (
                     name.to_string(),
-                    Tooltip {
-                        background: get_color_name(&mut color, &t.background),
-                        text: get_color_name(&mut color, &t.text),
-                        border: get_border_name(&mut color, &mut border, &t.border),
-                    },
+                    TooltipComponent::Defined(Tooltip {
+                        background: get_color_name(&color, &t.background),
+                        text: get_color_name(&color, &t.text),
+                        border: get_border_name(&color, &border, &t.border),
+                    }),
                 )
             })
-            .collect::<HashMap<String, Tooltip>>();
+            .collect::<HashMap<String, TooltipComponent>>();
+
+        let fallback = theme
+            .fallback
+            .iter()
+            .map(|(category, rules)| {
+                let rules = rules
+                    .iter()
+                    .map(|(state, rule)| {
+                        let apply = rule
+                            .operations
+                            .iter()
+                            .map(|operation| operation.to_string())
+                            .collect::<Vec<String>>()
+                            .join(", ");
+
+                        (
+                            state.to_string(),
+                            Fallback {
+                                from: rule.from.clone(),
+                                apply,
+                            },
+                        )
+                    })
+                    .collect::<HashMap<String, Fallback>>();
+
+                (category.to_string(), rules)
+            })
+            .collect::<HashMap<String, HashMap<String, Fallback>>>();
+
+        let tokens = theme
+            .tokens
+            .iter()
+            .filter(|(name, _)| own(name))
+            .map(|(name, value)| (name.clone(), Number::Value(*value)))
+            .collect::<HashMap<String, Number>>();
+
+        let application = Application {
+            background_color: get_color_name(&color, &theme.application.background_color),
+            text_color: get_color_name(&color, &theme.application.text_color),
+            text_size: theme.application.text_size.map(|size| (size as f32).into()),
+            font: theme.application.font.clone(),
+            defaults: theme.application.defaults.clone(),
+        };
 
         Self {
             name: theme.name.to_string(),
             description: theme.description.to_string(),
-            color: color.clone(),
-            border: border.clone(),
-            application: Application {
-                background_color: get_color_name(&mut color, &theme.application.background_color),
-                text_color: get_color_name(&mut color, &theme.application.text_color),
-            },
+            application,
+            aliases: HashMap::new(),
+            include: Vec::new(),
+            color,
+            border,
             button,
             container,
+            fallback,
+            palette,
             panegrid,
             picklist,
             progressbar,
             scrollable,
+            scopes: HashMap::new(),
+            text,
             textinput,
+            tokens,
             tooltip,
+            variants: HashMap::new(),
+            conditions: Vec::new(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn theme() -> NormalTheme {
+        let theme: Theme = ron::from_str(
+            r##"(
+                name: "test",
+                description: "",
+                application: (background_color: "white", text_color: "ink"),
+                aliases: { "old": "primary" },
+                color: { "white": "#ffffff", "black": "#000000", "ink": "#000000" },
+                border: {
+                    "thin": (color: "ink", radius: 1.0, width: 1.0),
+                    "line": (color: "black", radius: 1.0, width: 1.0),
+                },
+                button: {
+                    "old": (active: (background: "white", text: "ink", border: "thin")),
+                },
+            )"##,
+        )
+        .unwrap();
+
+        let mut theme = NormalTheme::parse(&theme).unwrap();
+
+        let plugin: Fragment =
+            ron::from_str(r##"(color: { "accent": "#ff0000" }, text: { "label": "accent" })"##)
+                .unwrap();
+        theme.register_namespace("plugin", plugin).unwrap();
+
+        theme
+    }
+
+    #[test]
+    fn own_entries() {
+        let serial = Theme::from(theme());
+
+        assert!(serial.color.keys().all(|name| !name.contains("::")));
+        assert!(serial.text.is_empty());
+        assert!(serial.button.contains_key("primary"));
+        assert!(!serial.button.contains_key("old"));
+    }
+
+    #[test]
+    fn sorted_names() {
+        let serial = Theme::from(theme());
+
+        assert_eq!(serial.application.text_color, "black");
+        assert!(matches!(
+            &serial.border["thin"],
+            BorderComponent::Defined(border) if border.color == "black"
+        ));
+
+        let button = serial.button["primary"].states();
+        let state = match &button.active {
+            ButtonComponent::Defined(state) => state,
+            _ => panic!("the button state is not defined"),
+        };
+
+        assert_eq!(state.text, "black");
+        assert!(matches!(&state.border, BorderValue::Key(key) if key == "line"));
+    }
+
+    #[test]
+    fn round_trip() {
+        let theme = theme();
+        let serialized = ron::to_string(&Theme::from(theme.clone())).unwrap();
+        let parsed = NormalTheme::parse(&ron::from_str(&serialized).unwrap()).unwrap();
+
+        assert_eq!(parsed.color.len(), 3);
+        assert_eq!(parsed.border["thin"], theme.border["thin"]);
+        assert_eq!(
+            format!("{:?}", parsed.button["primary"].state),
+            format!("{:?}", theme.button["primary"].state)
+        );
+        assert_eq!(
+            ron::to_string(&Theme::from(parsed)).unwrap().len(),
+            serialized.len()
+        );
+    }
+}
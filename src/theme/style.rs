@@ -1,72 +1,399 @@
-use super::Theme;
+//! `StyleSheet` implementations that allow using a `Theme` directly as an `iced` theme.
+//!
+//! Every style is selected by its key in the corresponding map of the theme.
+//! An empty key uses the default style of the widget defined in the application theme.
+//! Dotted keys fall back to their less specific levels (e.g. `"primary.small"` to `"primary"`).
+//! An unknown key uses the built-in `iced` style generated from the theme.
+
+use super::{cascade, levels, StyleId, Theme};
 use iced::{
-    widget::{button, container, text_input},
-    Vector,
+    application,
+    overlay::menu,
+    widget::{
+        button, checkbox, container, pane_grid, pick_list, progress_bar, radio, rule, scrollable,
+        slider, text, text_input, toggler,
+    },
 };
+use iced_native::widget::svg;
+
+use std::collections::HashMap;
+
+/// Returns the default key of the widget if the given style key is empty.
+fn key<'a>(style: &'a str, default: &'a Option<String>) -> &'a str {
+    match default {
+        Some(default) if style.is_empty() => default,
+        _ => style,
+    }
+}
+
+/// Gets the style with the given key, or the default style of the widget if the key is empty.
+fn get<'a, T>(map: &'a HashMap<String, T>, style: &str, default: &Option<String>) -> Option<&'a T> {
+    cascade(map, key(style, default))
+}
+
+/// The application is styled with the container keys.
+/// An unknown key uses the background and text colors of the application theme.
+impl application::StyleSheet for Theme {
+    type Style = String;
+
+    fn appearance(&self, style: &Self::Style) -> application::Appearance {
+        let text = self.application.text_color;
+
+        match get(&self.container, style, &self.application.defaults.application) {
+            Some(t) => application::Appearance {
+                background_color: t.color.into(),
+                text_color: t.text.unwrap_or(text).into(),
+            },
+            _ => application::Appearance {
+                background_color: self.application.background_color.into(),
+                text_color: text.into(),
+            },
+        }
+    }
+}
 
 impl button::StyleSheet for Theme {
     type Style = String;
 
     fn active(&self, style: &Self::Style) -> button::Appearance {
-        let t = self.button.get(style).unwrap();
-        button::Appearance {
-            shadow_offset: Vector::default(),
-            background: Some(t.state[0].background.into()),
-            border_radius: t.state[0].border.radius,
-            border_width: t.state[0].border.width,
-            border_color: t.state[0].border.color.into(),
-            text_color: t.state[0].text.into(),
+        match get(&self.button, style, &self.application.defaults.button) {
+            Some(t) => t.state[0].into(),
+            _ => button::StyleSheet::active(&self.builtin(), &Default::default()),
         }
     }
 
     fn hovered(&self, style: &Self::Style) -> button::Appearance {
-        let t = self.button.get(style).unwrap();
-        button::Appearance {
-            shadow_offset: Vector::default(),
-            background: Some(t.state[1].background.into()),
-            border_radius: t.state[1].border.radius,
-            border_width: t.state[1].border.width,
-            border_color: t.state[1].border.color.into(),
-            text_color: t.state[1].text.into(),
+        match get(&self.button, style, &self.application.defaults.button) {
+            Some(t) => t.state[1].into(),
+            _ => button::StyleSheet::hovered(&self.builtin(), &Default::default()),
         }
     }
 
     fn pressed(&self, style: &Self::Style) -> button::Appearance {
-        let t = self.button.get(style).unwrap();
-        button::Appearance {
-            shadow_offset: Vector::default(),
-            background: Some(t.state[2].background.into()),
-            border_radius: t.state[2].border.radius,
-            border_width: t.state[2].border.width,
-            border_color: t.state[2].border.color.into(),
-            text_color: t.state[2].text.into(),
+        match get(&self.button, style, &self.application.defaults.button) {
+            Some(t) => t.state[2].into(),
+            _ => button::StyleSheet::pressed(&self.builtin(), &Default::default()),
         }
     }
 
     fn disabled(&self, style: &Self::Style) -> button::Appearance {
-        let t = self.button.get(style).unwrap();
-        button::Appearance {
-            shadow_offset: Vector::default(),
-            background: Some(t.state[3].background.into()),
-            border_radius: t.state[3].border.radius,
-            border_width: t.state[3].border.width,
-            border_color: t.state[3].border.color.into(),
-            text_color: t.state[3].text.into(),
+        match get(&self.button, style, &self.application.defaults.button) {
+            Some(t) => t.state[3].into(),
+            _ => button::StyleSheet::disabled(&self.builtin(), &Default::default()),
+        }
+    }
+}
+
+/// Checkboxes are styled with the button keys.
+/// Unchecked boxes use the active and hovered states, checked boxes use the pressed state.
+impl checkbox::StyleSheet for Theme {
+    type Style = String;
+
+    fn active(&self, style: &Self::Style, is_checked: bool) -> checkbox::Appearance {
+        match get(&self.button, style, &self.application.defaults.checkbox) {
+            Some(t) => {
+                let state = if is_checked { t.state[2] } else { t.state[0] };
+                checkbox::Appearance {
+                    background: state.background.into(),
+                    checkmark_color: state.text.into(),
+                    border_radius: state.border.radius,
+                    border_width: state.border.width,
+                    border_color: state.border.color.into(),
+                    text_color: None,
+                }
+            }
+            _ => checkbox::StyleSheet::active(&self.builtin(), &Default::default(), is_checked),
+        }
+    }
+
+    fn hovered(&self, style: &Self::Style, is_checked: bool) -> checkbox::Appearance {
+        match get(&self.button, style, &self.application.defaults.checkbox) {
+            Some(t) => {
+                let state = if is_checked { t.state[2] } else { t.state[1] };
+                checkbox::Appearance {
+                    background: state.background.into(),
+                    checkmark_color: state.text.into(),
+                    border_radius: state.border.radius,
+                    border_width: state.border.width,
+                    border_color: state.border.color.into(),
+                    text_color: None,
+                }
+            }
+            _ => checkbox::StyleSheet::hovered(&self.builtin(), &Default::default(), is_checked),
         }
     }
 }
 
-impl iced_native::widget::container::StyleSheet for Theme {
+/// Tooltips are styled as containers, so the tooltip keys are used
+/// when no container is defined with the given key at the same level.
+impl container::StyleSheet for Theme {
     type Style = String;
 
     fn appearance(&self, style: &Self::Style) -> container::Appearance {
-        let t = self.container.get(style).unwrap();
-        container::Appearance {
-            text_color: None,
-            background: None,
-            border_radius: t.border.radius,
-            border_width: t.border.width,
-            border_color: t.border.color.into(),
+        let style = key(style, &self.application.defaults.container);
+
+        let appearance = levels(style).find_map(|style| {
+            match (self.container.get(style), self.tooltip.get(style)) {
+                (Some(t), _) => Some((*t).into()),
+                (_, Some(t)) => Some((*t).into()),
+                _ => None,
+            }
+        });
+
+        match appearance {
+            Some(appearance) => appearance,
+            _ => container::StyleSheet::appearance(&self.builtin(), &Default::default()),
+        }
+    }
+}
+
+/// The menu of a picklist shares the key of the picklist that opened it.
+impl menu::StyleSheet for Theme {
+    type Style = String;
+
+    fn appearance(&self, style: &Self::Style) -> menu::Appearance {
+        match get(&self.picklist, style, &self.application.defaults.picklist) {
+            Some(t) => t.menu.into(),
+            _ => menu::StyleSheet::appearance(&self.builtin(), &Default::default()),
+        }
+    }
+}
+
+impl pane_grid::StyleSheet for Theme {
+    type Style = String;
+
+    fn picked_split(&self, style: &Self::Style) -> Option<pane_grid::Line> {
+        match get(&self.panegrid, style, &self.application.defaults.panegrid) {
+            Some(t) => Some(t.state[0].into()),
+            _ => pane_grid::StyleSheet::picked_split(&self.builtin(), &Default::default()),
+        }
+    }
+
+    fn hovered_split(&self, style: &Self::Style) -> Option<pane_grid::Line> {
+        match get(&self.panegrid, style, &self.application.defaults.panegrid) {
+            Some(t) => Some(t.state[1].into()),
+            _ => pane_grid::StyleSheet::hovered_split(&self.builtin(), &Default::default()),
+        }
+    }
+}
+
+impl pick_list::StyleSheet for Theme {
+    type Style = String;
+
+    fn active(&self, style: &Self::Style) -> pick_list::Appearance {
+        match get(&self.picklist, style, &self.application.defaults.picklist) {
+            Some(t) => t.state[0].into(),
+            _ => pick_list::StyleSheet::active(&self.builtin(), &Default::default()),
+        }
+    }
+
+    fn hovered(&self, style: &Self::Style) -> pick_list::Appearance {
+        match get(&self.picklist, style, &self.application.defaults.picklist) {
+            Some(t) => t.state[1].into(),
+            _ => pick_list::StyleSheet::hovered(&self.builtin(), &Default::default()),
+        }
+    }
+}
+
+impl progress_bar::StyleSheet for Theme {
+    type Style = String;
+
+    fn appearance(&self, style: &Self::Style) -> progress_bar::Appearance {
+        match get(
+            &self.progressbar,
+            style,
+            &self.application.defaults.progressbar,
+        ) {
+            Some(t) => (*t).into(),
+            _ => progress_bar::StyleSheet::appearance(&self.builtin(), &Default::default()),
+        }
+    }
+}
+
+/// Radio buttons are styled with the button keys.
+/// Unselected radios use the active and hovered states, selected radios use the pressed state.
+impl radio::StyleSheet for Theme {
+    type Style = String;
+
+    fn active(&self, style: &Self::Style, is_selected: bool) -> radio::Appearance {
+        match get(&self.button, style, &self.application.defaults.radio) {
+            Some(t) => {
+                let state = if is_selected { t.state[2] } else { t.state[0] };
+                radio::Appearance {
+                    background: state.background.into(),
+                    dot_color: state.text.into(),
+                    border_width: state.border.width,
+                    border_color: state.border.color.into(),
+                    text_color: None,
+                }
+            }
+            _ => radio::StyleSheet::active(&self.builtin(), &Default::default(), is_selected),
+        }
+    }
+
+    fn hovered(&self, style: &Self::Style, is_selected: bool) -> radio::Appearance {
+        match get(&self.button, style, &self.application.defaults.radio) {
+            Some(t) => {
+                let state = if is_selected { t.state[2] } else { t.state[1] };
+                radio::Appearance {
+                    background: state.background.into(),
+                    dot_color: state.text.into(),
+                    border_width: state.border.width,
+                    border_color: state.border.color.into(),
+                    text_color: None,
+                }
+            }
+            _ => radio::StyleSheet::hovered(&self.builtin(), &Default::default(), is_selected),
+        }
+    }
+}
+
+/// Rules are styled with the border keys.
+impl rule::StyleSheet for Theme {
+    type Style = String;
+
+    fn appearance(&self, style: &Self::Style) -> rule::Appearance {
+        match get(&self.border, style, &self.application.defaults.rule) {
+            Some(t) => rule::Appearance {
+                color: t.color.into(),
+                width: t.width.round() as u16,
+                radius: t.radius,
+                fill_mode: rule::FillMode::Full,
+            },
+            _ => rule::StyleSheet::appearance(&self.builtin(), &Default::default()),
+        }
+    }
+}
+
+impl scrollable::StyleSheet for Theme {
+    type Style = String;
+
+    fn active(&self, style: &Self::Style) -> scrollable::Scrollbar {
+        match get(
+            &self.scrollable,
+            style,
+            &self.application.defaults.scrollable,
+        ) {
+            Some(t) => t.state[0].into(),
+            _ => scrollable::StyleSheet::active(&self.builtin(), &Default::default()),
+        }
+    }
+
+    fn hovered(&self, style: &Self::Style) -> scrollable::Scrollbar {
+        match get(
+            &self.scrollable,
+            style,
+            &self.application.defaults.scrollable,
+        ) {
+            Some(t) => t.state[1].into(),
+            _ => scrollable::StyleSheet::hovered(&self.builtin(), &Default::default()),
+        }
+    }
+
+    fn dragging(&self, style: &Self::Style) -> scrollable::Scrollbar {
+        match get(
+            &self.scrollable,
+            style,
+            &self.application.defaults.scrollable,
+        ) {
+            Some(t) => t.state[2].into(),
+            _ => scrollable::StyleSheet::dragging(&self.builtin(), &Default::default()),
+        }
+    }
+
+    fn active_horizontal(&self, style: &Self::Style) -> scrollable::Scrollbar {
+        match get(
+            &self.scrollable,
+            style,
+            &self.application.defaults.scrollable,
+        ) {
+            Some(t) => t.horizontal[0].into(),
+            _ => scrollable::StyleSheet::active_horizontal(&self.builtin(), &Default::default()),
+        }
+    }
+
+    fn hovered_horizontal(&self, style: &Self::Style) -> scrollable::Scrollbar {
+        match get(
+            &self.scrollable,
+            style,
+            &self.application.defaults.scrollable,
+        ) {
+            Some(t) => t.horizontal[1].into(),
+            _ => scrollable::StyleSheet::hovered_horizontal(&self.builtin(), &Default::default()),
+        }
+    }
+
+    fn dragging_horizontal(&self, style: &Self::Style) -> scrollable::Scrollbar {
+        match get(
+            &self.scrollable,
+            style,
+            &self.application.defaults.scrollable,
+        ) {
+            Some(t) => t.horizontal[2].into(),
+            _ => scrollable::StyleSheet::dragging_horizontal(&self.builtin(), &Default::default()),
+        }
+    }
+}
+
+/// Sliders are styled with the button keys.
+/// In order, the active, hovered and pressed states style the active, hovered and dragging sliders.
+impl slider::StyleSheet for Theme {
+    type Style = String;
+
+    fn active(&self, style: &Self::Style) -> slider::Appearance {
+        match get(&self.button, style, &self.application.defaults.slider) {
+            Some(t) => slider_appearance(&t.state[0]),
+            _ => slider::StyleSheet::active(&self.builtin(), &Default::default()),
+        }
+    }
+
+    fn hovered(&self, style: &Self::Style) -> slider::Appearance {
+        match get(&self.button, style, &self.application.defaults.slider) {
+            Some(t) => slider_appearance(&t.state[1]),
+            _ => slider::StyleSheet::hovered(&self.builtin(), &Default::default()),
+        }
+    }
+
+    fn dragging(&self, style: &Self::Style) -> slider::Appearance {
+        match get(&self.button, style, &self.application.defaults.slider) {
+            Some(t) => slider_appearance(&t.state[2]),
+            _ => slider::StyleSheet::dragging(&self.builtin(), &Default::default()),
+        }
+    }
+}
+
+fn slider_appearance(state: &crate::ButtonState) -> slider::Appearance {
+    slider::Appearance {
+        rail_colors: (state.background.into(), state.border.color.into()),
+        handle: slider::Handle {
+            shape: slider::HandleShape::Rectangle {
+                width: 8,
+                border_radius: state.border.radius,
+            },
+            color: state.background.into(),
+            border_width: state.border.width,
+            border_color: state.border.color.into(),
+        },
+    }
+}
+
+/// Svgs are tinted with the text style keys.
+impl svg::StyleSheet for Theme {
+    type Style = String;
+
+    fn appearance(&self, style: &Self::Style) -> svg::Appearance {
+        svg::Appearance {
+            color: get(&self.text, style, &self.application.defaults.svg).map(|c| c.into()),
+        }
+    }
+}
+
+impl text::StyleSheet for Theme {
+    type Style = StyleId;
+
+    fn appearance(&self, style: Self::Style) -> text::Appearance {
+        text::Appearance {
+            color: get(&self.text, style.0, &self.application.defaults.text).map(|c| c.into()),
         }
     }
 }
@@ -75,47 +402,116 @@ impl text_input::StyleSheet for Theme {
     type Style = String;
 
     fn active(&self, style: &Self::Style) -> text_input::Appearance {
-        let t = self.textinput.get(style).unwrap();
-        text_input::Appearance {
-            background: t.state[0].background.into(),
-            border_radius: t.state[0].border.radius,
-            border_width: t.state[0].border.width,
-            border_color: t.state[0].border.color.into(),
+        match get(&self.textinput, style, &self.application.defaults.textinput) {
+            Some(t) => t.state[0].into(),
+            _ => text_input::StyleSheet::active(&self.builtin(), &Default::default()),
         }
     }
 
     fn hovered(&self, style: &Self::Style) -> text_input::Appearance {
-        let t = self.textinput.get(style).unwrap();
-        text_input::Appearance {
-            background: t.state[1].background.into(),
-            border_radius: t.state[1].border.radius,
-            border_width: t.state[1].border.width,
-            border_color: t.state[1].border.color.into(),
+        match get(&self.textinput, style, &self.application.defaults.textinput) {
+            Some(t) => t.state[1].into(),
+            _ => text_input::StyleSheet::hovered(&self.builtin(), &Default::default()),
         }
     }
 
     fn focused(&self, style: &Self::Style) -> text_input::Appearance {
-        let t = self.textinput.get(style).unwrap();
-        text_input::Appearance {
-            background: t.state[2].background.into(),
-            border_radius: t.state[2].border.radius,
-            border_width: t.state[2].border.width,
-            border_color: t.state[2].border.color.into(),
+        match get(&self.textinput, style, &self.application.defaults.textinput) {
+            Some(t) => t.state[2].into(),
+            _ => text_input::StyleSheet::focused(&self.builtin(), &Default::default()),
         }
     }
 
     fn placeholder_color(&self, style: &Self::Style) -> iced::Color {
-        let t = self.textinput.get(style).unwrap();
-        t.colors[0].into()
+        match get(&self.textinput, style, &self.application.defaults.textinput) {
+            Some(t) => t.colors[0].into(),
+            _ => text_input::StyleSheet::placeholder_color(&self.builtin(), &Default::default()),
+        }
     }
 
     fn value_color(&self, style: &Self::Style) -> iced::Color {
-        let t = self.textinput.get(style).unwrap();
-        t.colors[1].into()
+        match get(&self.textinput, style, &self.application.defaults.textinput) {
+            Some(t) => t.colors[1].into(),
+            _ => text_input::StyleSheet::value_color(&self.builtin(), &Default::default()),
+        }
     }
 
     fn selection_color(&self, style: &Self::Style) -> iced::Color {
-        let t = self.textinput.get(style).unwrap();
-        t.colors[2].into()
+        match get(&self.textinput, style, &self.application.defaults.textinput) {
+            Some(t) => t.colors[2].into(),
+            _ => text_input::StyleSheet::selection_color(&self.builtin(), &Default::default()),
+        }
+    }
+}
+
+/// Togglers are styled with the button keys.
+/// Untoggled togglers use the active and hovered states, toggled togglers use the pressed state.
+impl toggler::StyleSheet for Theme {
+    type Style = String;
+
+    fn active(&self, style: &Self::Style, is_active: bool) -> toggler::Appearance {
+        match get(&self.button, style, &self.application.defaults.toggler) {
+            Some(t) => toggler_appearance(&t.state[if is_active { 2 } else { 0 }]),
+            _ => toggler::StyleSheet::active(&self.builtin(), &Default::default(), is_active),
+        }
+    }
+
+    fn hovered(&self, style: &Self::Style, is_active: bool) -> toggler::Appearance {
+        match get(&self.button, style, &self.application.defaults.toggler) {
+            Some(t) => toggler_appearance(&t.state[if is_active { 2 } else { 1 }]),
+            _ => toggler::StyleSheet::hovered(&self.builtin(), &Default::default(), is_active),
+        }
+    }
+}
+
+fn toggler_appearance(state: &crate::ButtonState) -> toggler::Appearance {
+    toggler::Appearance {
+        background: state.background.into(),
+        background_border: Some(state.border.color.into()),
+        foreground: state.text.into(),
+        foreground_border: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serial;
+
+    fn theme(defaults: &str) -> Theme {
+        let theme: serial::Theme = ron::from_str(&format!(
+            r##"(
+                name: "test",
+                description: "",
+                application: (background_color: "white", text_color: "black", defaults: {}),
+                color: {{ "white": "#ffffff", "black": "#000000", "red": "#ff0000" }},
+                border: {{ "thin": (color: "black", radius: 1.0, width: 1.0) }},
+                container: {{ "window": (color: "red", border: "thin") }},
+            )"##,
+            defaults
+        ))
+        .unwrap();
+
+        Theme::parse(&theme).unwrap()
+    }
+
+    #[test]
+    fn application_keys() {
+        let theme = theme("()");
+
+        let window = application::StyleSheet::appearance(&theme, &"window".into());
+        assert_eq!(window.background_color, iced::Color::from_rgb8(255, 0, 0));
+        assert_eq!(window.text_color, iced::Color::BLACK);
+
+        let unknown = application::StyleSheet::appearance(&theme, &"missing".into());
+        assert_eq!(unknown.background_color, iced::Color::WHITE);
+    }
+
+    #[test]
+    fn application_default() {
+        let theme = theme(r#"(application: Some("window"))"#);
+
+        let default = application::StyleSheet::appearance(&theme, &String::new());
+        assert_eq!(default.background_color, iced::Color::from_rgb8(255, 0, 0));
     }
 }
@@ -0,0 +1,101 @@
+//! Variants of a theme (e.g. light, dark or high contrast).
+//!
+//! A variant is a fragment that overrides the entries of the base theme, usually its colors.
+//! The entries that a variant does not override are shared with the base theme.
+
+use super::{serial, Context, Fragment, Options, Theme};
+use crate::Error;
+
+impl serial::Theme {
+    /// Returns the names of the variants declared by this theme, sorted by name.
+    pub fn variants(&self) -> Vec<&str> {
+        let mut variants: Vec<&str> = self.variants.keys().map(String::as_str).collect();
+        variants.sort_unstable();
+        variants
+    }
+
+    /// Creates the &serialized theme of a variant, overriding the base entries with the entries
+    /// of the variant.
+    pub fn variant(&self, name: &str) -> Result<Self, Error> {
+        let variant = self
+            .variants
+            .get(name)
+            .ok_or_else(|| Error::UnknownVariant(name.into()))?;
+
+        let mut theme = self.clone();
+        theme.apply(variant.clone());
+
+        Ok(theme)
+    }
+
+    /// Overrides the entries of this theme with the entries of a fragment.
+    pub(super) fn apply(&mut self, fragment: Fragment) {
+        self.border.extend(fragment.border);
+        self.button.extend(fragment.button);
+        self.color.extend(fragment.color);
+        self.container.extend(fragment.container);
+        self.fallback.extend(fragment.fallback);
+        self.panegrid.extend(fragment.panegrid);
+        self.picklist.extend(fragment.picklist);
+        self.progressbar.extend(fragment.progressbar);
+        self.scrollable.extend(fragment.scrollable);
+        self.text.extend(fragment.text);
+        self.textinput.extend(fragment.textinput);
+        self.tokens.extend(fragment.tokens);
+        self.tooltip.extend(fragment.tooltip);
+    }
+}
+
+impl Theme {
+    /// Creates a variant of a theme from its &serialized version, or the base theme if no
+    /// variant is given. The conditional overrides take precedence over the entries of the
+    /// variant.
+    pub(super) fn create_variant(
+        theme: &serial::Theme,
+        variant: Option<&str>,
+        context: &Context,
+    ) -> Result<Self, Error> {
+        let variant = match variant {
+            Some(variant) => variant,
+            _ => return Self::create(theme, context),
+        };
+
+        let mut new_theme = Self::create(&theme.variant(variant)?, context)?;
+        new_theme.variant = Some(variant.into());
+
+        for scope in new_theme.scopes.values_mut() {
+            scope.variant = Some(variant.into());
+        }
+
+        Ok(new_theme)
+    }
+
+    /// Switches to another variant of the &serialized theme this theme was created from, or to
+    /// the base theme if the options do not select a variant. The conditional overrides are
+    /// evaluated against the context of this theme, unless the options give another one, and
+    /// the namespaces registered in this theme and the aliases applied to it are registered and
+    /// applied again, in that order.
+    /// The theme is not modified if the variant fails.
+    pub fn switch(&mut self, theme: &serial::Theme, options: &Options) -> Result<(), Error> {
+        let options = Options {
+            context: Some(options.context.clone().unwrap_or_else(|| self.context.clone())),
+            ..options.clone()
+        };
+
+        let mut new_theme = Self::parse_with(theme, &options)?;
+
+        // Register the namespaces in a fixed order, as they may reference each other.
+        let mut namespaces: Vec<_> = self.namespaces.iter().collect();
+        namespaces.sort_unstable_by_key(|(namespace, _)| namespace.as_str());
+
+        for (namespace, fragment) in namespaces {
+            new_theme.register_namespace(namespace, fragment.clone())?;
+        }
+
+        new_theme.alias(&self.aliases);
+
+        *self = new_theme;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,318 @@
+//! Numeric design tokens.
+//!
+//! Tokens are named numbers (e.g. `"radius.md"`) that can be referenced by any numeric field,
+//! either directly or in arithmetic expressions with `+`, `-`, `*`, `/` and parentheses.
+//! A key with dashes (e.g. `"radius-md"`) can only be referenced directly, as a dash in an
+//! expression is a subtraction.
+
+pub mod serial;
+
+use serial::Number;
+
+use std::collections::HashMap;
+
+/// Resolves all the tokens, which may reference other tokens or the already resolved tokens.
+/// Returns the name of a token that could not be resolved, due to missing keys, invalid
+/// expressions or circular references.
+pub(crate) fn resolve(
+    tokens: &HashMap<String, Number>,
+    mut resolved: HashMap<String, f32>,
+) -> Result<HashMap<String, f32>, String> {
+    // Each pass resolves the tokens whose references are already resolved.
+    loop {
+        let mut changed = false;
+
+        for (name, number) in tokens {
+            if resolved.contains_key(name) {
+                continue;
+            }
+
+            if let Ok(value) = evaluate(number, &resolved) {
+                resolved.insert(name.clone(), value);
+                changed = true;
+            }
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    match tokens.keys().find(|name| !resolved.contains_key(*name)) {
+        Some(name) => Err(name.clone()),
+        _ => Ok(resolved),
+    }
+}
+
+/// Evaluates a number with the given tokens.
+pub(crate) fn evaluate(number: &Number, tokens: &HashMap<String, f32>) -> Result<f32, ()> {
+    match number {
+        Number::Value(value) => Ok(*value),
+
+        // A key with dashes (e.g. `"radius-md"`) is not an expression, so it can be referenced.
+        Number::Expression(expression) if tokens.contains_key(expression.trim()) => {
+            Ok(tokens[expression.trim()])
+        }
+
+        Number::Expression(expression) => {
+            let mut parser = Parser {
+                chars: expression.chars().peekable(),
+                tokens,
+            };
+
+            let value = parser.sum()?;
+
+            match parser.next() {
+                None => Ok(value),
+                _ => Err(()),
+            }
+        }
+    }
+}
+
+/// Recursive descent parser of arithmetic expressions.
+struct Parser<'a, I: Iterator<Item = char>> {
+    /// Remaining characters of the expression.
+    chars: core::iter::Peekable<I>,
+
+    /// Resolved tokens.
+    tokens: &'a HashMap<String, f32>,
+}
+
+impl<'a, I: Iterator<Item = char>> Parser<'a, I> {
+    /// Returns the next character that is not a whitespace.
+    fn peek(&mut self) -> Option<char> {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+
+        self.chars.peek().copied()
+    }
+
+    /// Consumes the next character that is not a whitespace.
+    fn next(&mut self) -> Option<char> {
+        self.peek()?;
+        self.chars.next()
+    }
+
+    /// Parses a sum or subtraction of products.
+    fn sum(&mut self) -> Result<f32, ()> {
+        let mut value = self.product()?;
+
+        loop {
+            match self.peek() {
+                Some('+') => {
+                    self.next();
+                    value += self.product()?;
+                }
+
+                Some('-') => {
+                    self.next();
+                    value -= self.product()?;
+                }
+
+                _ => return Ok(value),
+            }
+        }
+    }
+
+    /// Parses a product or division of factors.
+    fn product(&mut self) -> Result<f32, ()> {
+        let mut value = self.factor()?;
+
+        loop {
+            match self.peek() {
+                Some('*') => {
+                    self.next();
+                    value *= self.factor()?;
+                }
+
+                Some('/') => {
+                    self.next();
+                    value /= self.factor()?;
+                }
+
+                _ => return Ok(value),
+            }
+        }
+    }
+
+    /// Parses a number, a token key, a negation or an expression between parentheses.
+    fn factor(&mut self) -> Result<f32, ()> {
+        match self.peek().ok_or(())? {
+            '-' => {
+                self.next();
+                Ok(-self.factor()?)
+            }
+
+            '(' => {
+                self.next();
+                let value = self.sum()?;
+
+                match self.next() {
+                    Some(')') => Ok(value),
+                    _ => Err(()),
+                }
+            }
+
+            c if c.is_ascii_digit() || (c == '.') => {
+                let mut number = String::new();
+
+                while let Some(c) = self.chars.next_if(|c| c.is_ascii_digit() || (*c == '.')) {
+                    number.push(c);
+                }
+
+                number.parse().map_err(|_| ())
+            }
+
+            c if c.is_alphabetic() || (c == '_') => {
+                let mut key = String::new();
+
+                while let Some(c) = self
+                    .chars
+                    .next_if(|c| c.is_alphanumeric() || (*c == '_') || (*c == '.'))
+                {
+                    key.push(c);
+                }
+
+                self.tokens.get(&key).copied().ok_or(())
+            }
+
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(expression: &str, tokens: &HashMap<String, f32>) -> Result<f32, ()> {
+        evaluate(&Number::Expression(expression.into()), tokens)
+    }
+
+    fn tokens(tokens: &[(&str, Number)]) -> HashMap<String, Number> {
+        tokens
+            .iter()
+            .map(|(name, number)| (name.to_string(), number.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn precedence() {
+        let empty = HashMap::new();
+
+        assert_eq!(eval("1 + 2 * 3", &empty), Ok(7.0));
+        assert_eq!(eval("8 - 4 / 2", &empty), Ok(6.0));
+        assert_eq!(eval("8 - 2 - 1", &empty), Ok(5.0));
+        assert_eq!(eval("8 / 2 / 2", &empty), Ok(2.0));
+        assert_eq!(eval("  1.5*2  ", &empty), Ok(3.0));
+    }
+
+    #[test]
+    fn parentheses() {
+        let empty = HashMap::new();
+
+        assert_eq!(eval("(1 + 2) * 3", &empty), Ok(9.0));
+        assert_eq!(eval("2 * ((1 + 1) * (4 - 1))", &empty), Ok(12.0));
+        assert_eq!(eval("(1 + 2", &empty), Err(()));
+        assert_eq!(eval("1 + 2)", &empty), Err(()));
+        assert_eq!(eval("()", &empty), Err(()));
+    }
+
+    #[test]
+    fn unary_minus() {
+        let empty = HashMap::new();
+
+        assert_eq!(eval("-2", &empty), Ok(-2.0));
+        assert_eq!(eval("-2 * -3", &empty), Ok(6.0));
+        assert_eq!(eval("4 - -2", &empty), Ok(6.0));
+        assert_eq!(eval("-(1 + 2)", &empty), Ok(-3.0));
+        assert_eq!(eval("--1", &empty), Ok(1.0));
+    }
+
+    #[test]
+    fn invalid() {
+        let empty = HashMap::new();
+
+        assert_eq!(eval("", &empty), Err(()));
+        assert_eq!(eval("1 +", &empty), Err(()));
+        assert_eq!(eval("1 2", &empty), Err(()));
+        assert_eq!(eval("1 % 2", &empty), Err(()));
+        assert_eq!(eval("1..2", &empty), Err(()));
+    }
+
+    #[test]
+    fn references() {
+        let resolved = resolve(
+            &tokens(&[
+                ("radius.md", Number::Value(4.0)),
+                ("radius.lg", Number::Expression("radius.md * 2".into())),
+                (
+                    "radius_xl",
+                    Number::Expression("radius.lg + radius.md".into()),
+                ),
+            ]),
+            HashMap::new(),
+        )
+        .unwrap();
+
+        assert_eq!(resolved["radius.lg"], 8.0);
+        assert_eq!(resolved["radius_xl"], 12.0);
+    }
+
+    #[test]
+    fn dashed_keys() {
+        let resolved = resolve(
+            &tokens(&[
+                ("radius-md", Number::Value(4.0)),
+                ("radius-lg", Number::Expression(" radius-md ".into())),
+            ]),
+            HashMap::new(),
+        )
+        .unwrap();
+
+        assert_eq!(resolved["radius-lg"], 4.0);
+
+        let dashed = HashMap::from([("a-b".to_string(), 4.0), ("a".to_string(), 1.0)]);
+        assert_eq!(eval("a-b", &dashed), Ok(4.0));
+        assert_eq!(eval("a - 1", &dashed), Ok(0.0));
+        assert_eq!(eval("a-b * 2", &dashed), Err(()));
+    }
+
+    #[test]
+    fn base_tokens() {
+        let base = HashMap::from([("base".to_string(), 2.0)]);
+        let resolved = resolve(
+            &tokens(&[("double", Number::Expression("base * 2".into()))]),
+            base,
+        )
+        .unwrap();
+
+        assert_eq!(resolved["double"], 4.0);
+        assert_eq!(resolved["base"], 2.0);
+    }
+
+    #[test]
+    fn unknown_tokens() {
+        let unknown = tokens(&[
+            ("a", Number::Value(1.0)),
+            ("b", Number::Expression("a + missing".into())),
+        ]);
+
+        assert_eq!(resolve(&unknown, HashMap::new()), Err("b".to_string()));
+        assert_eq!(eval("missing", &HashMap::new()), Err(()));
+    }
+
+    #[test]
+    fn circular_tokens() {
+        let circular = tokens(&[
+            ("a", Number::Expression("b + 1".into())),
+            ("b", Number::Expression("a + 1".into())),
+        ]);
+
+        let name = resolve(&circular, HashMap::new()).unwrap_err();
+        assert!((name == "a") || (name == "b"));
+
+        let own = tokens(&[("a", Number::Expression("a".into()))]);
+        assert_eq!(resolve(&own, HashMap::new()), Err("a".to_string()));
+    }
+}
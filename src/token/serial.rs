@@ -0,0 +1,20 @@
+//! `serde` compatible version of the numeric tokens.
+
+use serde::{Deserialize, Serialize};
+
+/// Numeric value of a style, given as a number or as an expression of tokens (e.g. `"radius.md * 2"`).
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum Number {
+    /// The number is defined.
+    Value(f32),
+
+    /// The number is the result of an arithmetic expression of numbers and token keys.
+    Expression(String),
+}
+
+impl From<f32> for Number {
+    fn from(value: f32) -> Self {
+        Number::Value(value)
+    }
+}
@@ -2,10 +2,15 @@
 
 pub mod serial;
 
-use crate::{Border, Color, Theme};
+use crate::{
+    theme::cross::{self, split, state, Shared},
+    Border, Color, Theme,
+};
 
 use iced::widget::container::{Appearance, StyleSheet};
 
+use serial::TooltipComponent;
+
 #[derive(Clone, Copy, Debug)]
 pub struct Tooltip {
     /// Background color.
@@ -20,24 +25,82 @@ pub struct Tooltip {
 
 impl Tooltip {
     /// Attempts to create a theme from its &serialized version.
-    pub fn create(serial: &serial::Tooltip, theme: &Theme) -> Result<Self, ()> {
-        // Get the background color.
-        let background = match theme.color.get(serial.background.as_str()) {
-            Some(color) => *color,
-            _ => return Err(()),
+    pub fn create(serial: &TooltipComponent, theme: &Theme) -> Result<Self, ()> {
+        // Inherit the shared fields of a style of another category.
+        if let Some((reference, _)) = serial.parent() {
+            if let (Some(category), name) = split(reference, "tooltip") {
+                let shared = theme.shared(category, name, state("tooltip", 0))?;
+                return Self::cross(serial, shared, theme);
+            }
+        }
+
+        match serial {
+            TooltipComponent::Defined(tooltip) => Self::from(tooltip, theme),
+
+            TooltipComponent::Inherited(name) => {
+                match theme.inherited("tooltip", &theme.tooltip, name) {
+                    Some(tooltip) => Ok(*tooltip),
+                    _ => Err(()),
+                }
+            }
+
+            TooltipComponent::Extends {
+                from,
+                background,
+                text,
+                border,
+            } => match theme.inherited("tooltip", &theme.tooltip, from) {
+                Some(tooltip) => {
+                    let mut tooltip = *tooltip;
+
+                    if let Some(key) = background {
+                        tooltip.background = theme.color_key(key)?;
+                    }
+
+                    if let Some(key) = text {
+                        tooltip.text = theme.color_key(key)?;
+                    }
+
+                    if let Some(key) = border {
+                        tooltip.border = theme.border_value(key)?;
+                    }
+
+                    Ok(tooltip)
+                }
+                _ => Err(()),
+            },
+        }
+    }
+
+    /// Creates a style from the shared fields of a style of another category.
+    fn cross(serial: &TooltipComponent, shared: Shared, theme: &Theme) -> Result<Tooltip, ()> {
+        let (background, text, border) = match serial {
+            TooltipComponent::Extends {
+                background,
+                text,
+                border,
+                ..
+            } => (background, text, border),
+            _ => (&None, &None, &None),
         };
 
+        Ok(Tooltip {
+            background: cross::color(theme, background, shared.background)?,
+            text: cross::color(theme, text, shared.text)?,
+            border: cross::border(theme, border, shared.border)?,
+        })
+    }
+
+    /// Attempts to create a theme from its &serialized definition.
+    fn from(serial: &serial::Tooltip, theme: &Theme) -> Result<Self, ()> {
+        // Get the background color.
+        let background = theme.color_key(&serial.background)?;
+
         // Get the text color.
-        let text = match theme.color.get(serial.text.as_str()) {
-            Some(color) => *color,
-            _ => return Err(()),
-        };
+        let text = theme.color_key(&serial.text)?;
 
         // Get the background color.
-        let border = match theme.border.get(serial.border.as_str()) {
-            Some(border) => *border,
-            _ => return Err(()),
-        };
+        let border = theme.border_value(&serial.border)?;
 
         Ok(Tooltip {
             background,
@@ -47,16 +110,28 @@ impl Tooltip {
     }
 }
 
+impl From<Tooltip> for iced::theme::Container {
+    fn from(tooltip: Tooltip) -> Self {
+        iced::theme::Container::Custom(Box::new(tooltip))
+    }
+}
+
 impl StyleSheet for Tooltip {
     type Style = iced::Theme;
 
     fn appearance(&self, _: &Self::Style) -> Appearance {
+        (*self).into()
+    }
+}
+
+impl From<Tooltip> for Appearance {
+    fn from(tooltip: Tooltip) -> Self {
         Appearance {
-            text_color: None,
-            background: Some(self.background.into()),
-            border_radius: self.border.radius,
-            border_width: self.border.width,
-            border_color: self.border.color.into(),
+            text_color: Some(tooltip.text.into()),
+            background: Some(tooltip.background.into()),
+            border_radius: tooltip.border.radius,
+            border_width: tooltip.border.width,
+            border_color: tooltip.border.color.into(),
         }
     }
 }
@@ -1,5 +1,7 @@
 //! `serde` compatible version of the tooltip theme.
 
+use crate::{component::compact, serial::BorderValue, theme::cross::overrides};
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -10,6 +12,61 @@ pub struct Tooltip {
     /// Key to the text color.
     pub text: String,
 
-    /// Key to the border definition.
-    pub border: String,
+    /// Key to the border definition, or inline border.
+    pub border: BorderValue,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(remote = "Self", tag = "type")]
+pub enum TooltipComponent {
+    /// The tooltip is inherited from another tooltip theme, overriding some of its fields.
+    Extends {
+        /// Key to the parent tooltip theme.
+        from: String,
+
+        /// Key to the background color.
+        #[serde(default)]
+        background: Option<String>,
+
+        /// Key to the text color.
+        #[serde(default)]
+        text: Option<String>,
+
+        /// Key to the border definition, or inline border.
+        #[serde(default)]
+        border: Option<BorderValue>,
+    },
+
+    /// The tooltip is defined.
+    Defined(Tooltip),
+
+    /// The tooltip is inherited from another tooltip theme.
+    Inherited(String),
+}
+
+compact!(TooltipComponent);
+
+impl TooltipComponent {
+    /// Returns the reference to the parent style and its overridden fields, if it is inherited.
+    pub(crate) fn parent(&self) -> Option<(&str, Vec<&'static str>)> {
+        match self {
+            TooltipComponent::Inherited(name) => Some((name, Vec::new())),
+
+            TooltipComponent::Extends {
+                from,
+                background,
+                text,
+                border,
+            } => Some((
+                from,
+                overrides(&[
+                    ("background", background.is_some()),
+                    ("text", text.is_some()),
+                    ("border", border.is_some()),
+                ]),
+            )),
+
+            TooltipComponent::Defined(_) => None,
+        }
+    }
 }
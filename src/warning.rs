@@ -0,0 +1,70 @@
+//! Warnings of the theme parsing, which do not prevent the theme from being created.
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Warning {
+    /// A style is defined with a key that was renamed, and is used through its alias.
+    Deprecated {
+        /// Category of the style.
+        category: &'static str,
+
+        /// Old key of the style.
+        name: String,
+
+        /// New key of the style.
+        replacement: String,
+    },
+
+    /// An entry is defined with both the old and the new key of an alias, so the entry of the
+    /// old key is not used through the alias.
+    Shadowed {
+        /// Category of the entry.
+        category: &'static str,
+
+        /// Old key of the entry.
+        name: String,
+
+        /// New key of the entry, which is already defined.
+        replacement: String,
+    },
+
+    /// The old key of an alias of the theme is not defined in any category.
+    UnknownAlias {
+        /// Old key of the alias.
+        name: String,
+
+        /// New key of the alias.
+        replacement: String,
+    },
+}
+
+impl core::fmt::Display for Warning {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Warning::Deprecated {
+                category,
+                name,
+                replacement,
+            } => write!(
+                f,
+                "{} \"{}\" is deprecated, rename it to \"{}\"",
+                category, name, replacement
+            ),
+
+            Warning::Shadowed {
+                category,
+                name,
+                replacement,
+            } => write!(
+                f,
+                "{} \"{}\" is not aliased, as \"{}\" is already defined",
+                category, name, replacement
+            ),
+
+            Warning::UnknownAlias { name, replacement } => write!(
+                f,
+                "alias \"{}\" to \"{}\" does not match any defined key",
+                name, replacement
+            ),
+        }
+    }
+}